@@ -42,7 +42,7 @@ pub fn register() -> Termenu {
                 }
 
                 // Create the command file
-                fs::write(&path, generate_command_template(&command_name)).map_err(|e| {
+                fs::write(path, generate_command_template(&command_name)).map_err(|e| {
                     TermenuError::connection_unknown_error(Some(json!( {
                         "issue": format!("Failed to create file: {}", e)
                     })))
@@ -53,11 +53,11 @@ pub fn register() -> Termenu {
                 let mod_line = format!("pub mod {}_command;\n", command_name.to_lowercase());
 
                 if mod_file_path.exists() {
-                    let mut content = fs::read_to_string(&mod_file_path).unwrap_or_default();
+                    let content = fs::read_to_string(mod_file_path).unwrap_or_default();
                     if !content.contains(&mod_line) {
                         let mut file = fs::OpenOptions::new()
                             .append(true)
-                            .open(&mod_file_path)
+                            .open(mod_file_path)
                             .map_err(|e| {
                                 TermenuError::connection_unknown_error(Some(json!( {
                                     "issue": format!("Failed to open mod.rs: {}", e)
@@ -99,20 +99,24 @@ pub fn register() -> Termenu {
         },
     );
 
-    command.add_option(
-        "--verbose",
-        "--verbose | Display detailed usage information.",
-    );
-    command.add_option(
-        "--add",
-        "--add=<command_name> | Specify the name of the command to add.",
-    );
+    // Listed in debug builds only; release builds reject it anyway
+    command
+        .category("Development")
+        .hidden_if(!cfg!(debug_assertions));
+    command
+        .add_option(
+            "--verbose",
+            "Print each step as files are created and registered.",
+        )
+        .flag();
+    command
+        .add_option("--add", "Name of the command to scaffold in src/commands/.")
+        .value();
 
     command
 }
 
 fn generate_command_template(name: &str) -> String {
-    let struct_name = format!("{}Command", capitalize_first_letter(&name.to_lowercase()));
     format!(
         "use crate::modules::termenu::Termenu;\n\n\
         pub fn register() -> Termenu {{\n    \
@@ -129,11 +133,3 @@ fn generate_command_template(name: &str) -> String {
         name
     )
 }
-
-fn capitalize_first_letter(s: &str) -> String {
-    let mut c = s.chars();
-    match c.next() {
-        None => String::new(),
-        Some(f) => f.to_uppercase().collect::<String>() + c.as_str(),
-    }
-}
@@ -1,8 +1,7 @@
-use crate::modules::termenu::{Termenu, TermenuError};
+use crate::modules::termenu::{Context, ErrorContext, Termenu, TermenuError};
 use colored::Colorize;
 use reqwest;
-use serde_json::{Value, json};
-use std::collections::HashMap;
+use serde_json::Value;
 use std::future::Future;
 use std::pin::Pin;
 
@@ -15,45 +14,24 @@ use std::pin::Pin;
 /// =========================================
 ///
 pub fn register() -> Termenu {
-    let mut inspire_cmd = Termenu::new_async_command(
+    let mut inspire_cmd = Termenu::new_async_command_with_context(
         "inspire",
         "Fetches and displays a random inspirational quote.",
-        |_options: HashMap<String, Option<String>>| {
+        |ctx: Context| {
             Box::pin(async move {
-                // Create a client that ignores SSL certificate validation
-                let client = reqwest::Client::builder()
-                    .danger_accept_invalid_certs(true)
-                    .build()
-                    .map_err(|e| {
-                        TermenuError::connection_unknown_error(Some(json!({
-                            "issue": format!("Client build failed: {}", e)
-                        })))
-                    })?;
+                // Shared HTTP client registered in `main`
+                let client = ctx.state::<reqwest::Client>()?;
 
-                // Send request to public quotes API
-                let resp = client
+                // Network and parse failures convert into TermenuError via `?`
+                let text = client
                     .get("https://api.quotable.io/random")
                     .send()
                     .await
-                    .map_err(|e| {
-                        TermenuError::connection_unknown_error(Some(json!({
-                            "issue": format!("Request failed: {}", e)
-                        })))
-                    })?;
-
-                // Convert response to text
-                let text = resp.text().await.map_err(|e| {
-                    TermenuError::connection_unknown_error(Some(json!({
-                        "issue": format!("Failed to read response: {}", e)
-                    })))
-                })?;
-
-                // Parse JSON
-                let resp_json: Value = serde_json::from_str(&text).map_err(|e| {
-                    TermenuError::input_unknown_error(Some(json!({
-                        "issue": format!("Invalid JSON: {}", e)
-                    })))
-                })?;
+                    .context("fetching quote")?
+                    .text()
+                    .await
+                    .context("reading quote")?;
+                let resp_json: Value = serde_json::from_str(&text).context("parsing quote")?;
 
                 let quote = resp_json["content"].as_str().unwrap_or("No quote found.");
                 let author = resp_json["author"].as_str().unwrap_or("Unknown");
@@ -66,10 +44,14 @@ pub fn register() -> Termenu {
         },
     );
 
-    inspire_cmd.add_option(
-        "--category",
-        "Specify a quote category (optional, not used in current API).",
-    );
+    // Network failures and unexpected responses
+    inspire_cmd.error_codes(&["E611", "E612", "E111"]);
+    inspire_cmd
+        .add_option(
+            "--category",
+            "Specify a quote category (optional, not used in current API).",
+        )
+        .value();
 
     inspire_cmd
 }
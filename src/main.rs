@@ -1,20 +1,59 @@
-mod commands;
-mod modules;
+use serde_json::json;
+use termenu::commands::{developer_command, hello_world_command, inspire_command};
+use termenu::modules::termenu::{AppInfo, State, Termenu, TermenuError, crash};
 
-use colored::Colorize;
-use commands::{developer_command, hello_world_command, inspire_command};
-use modules::termenu::Termenu;
-
-#[tokio::main]
-async fn main() {
-    match Termenu::processor(Termenu::validate_commands(vec![
+/// The app's own commands; built-ins are added by the processor
+fn commands() -> Vec<Termenu> {
+    vec![
         developer_command::register(),
         hello_world_command::register(),
         inspire_command::register(),
-    ]))
-    .await
-    {
+    ]
+}
+
+#[tokio::main]
+async fn main() {
+    // Help metadata is embedded at build time; adjust it here if needed
+    AppInfo::set(termenu::app_info!());
+
+    // Shared resources are built lazily, the first time a handler asks for them
+    let mut state = State::new();
+    state.register(|| {
+        reqwest::Client::builder()
+            .danger_accept_invalid_certs(true)
+            .build()
+            .map_err(|e| {
+                TermenuError::connection_unknown_error(Some(json!({
+                    "issue": format!("Client build failed: {}", e)
+                })))
+            })
+    });
+
+    // Run on a task so a panic comes back here as an error instead of unwinding out of main
+    let processor = tokio::spawn(async move {
+        let commands = Termenu::validate_commands(commands())?;
+        Termenu::processor_with_state(commands, state).await
+    });
+    let result = match processor.await {
+        Ok(result) => result,
+        // The crash hook already reported the panic
+        Err(_) => std::process::exit(crash::exit_code()),
+    };
+    match result {
         Ok(_) => {}
-        Err(e) => eprintln!("[{}] {}", "Error".red().bold(), e),
+        Err(e) => {
+            e.report();
+            std::process::exit(e.exit_code());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_commands_are_valid() {
+        Termenu::assert_valid(&commands());
     }
 }
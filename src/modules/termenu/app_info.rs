@@ -0,0 +1,97 @@
+use std::sync::RwLock;
+
+static APP_INFO: RwLock<Option<AppInfo>> = RwLock::new(None);
+
+/// Application metadata shown in help, embedded at build time
+#[derive(Debug, Clone, PartialEq)]
+pub struct AppInfo {
+    pub name: String,
+    /// Text rendered as the help banner
+    pub title: String,
+    pub version: String,
+    pub authors: Vec<String>,
+    pub description: String,
+}
+
+impl AppInfo {
+    /// Build from raw package values; `authors` is colon separated like `CARGO_PKG_AUTHORS`
+    pub fn new(name: &str, version: &str, authors: &str, description: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            title: "Termenu".to_string(),
+            version: version.to_string(),
+            authors: authors
+                .split(':')
+                .map(str::trim)
+                .filter(|a| !a.is_empty())
+                .map(str::to_string)
+                .collect(),
+            description: description.to_string(),
+        }
+    }
+
+    /// Package info of the crate Termenu was compiled in
+    ///
+    /// Apps depending on Termenu as a library should use `termenu::app_info!()`
+    /// so the values come from their own `Cargo.toml`.
+    pub fn from_package() -> Self {
+        Self::new(
+            env!("CARGO_PKG_NAME"),
+            env!("CARGO_PKG_VERSION"),
+            env!("CARGO_PKG_AUTHORS"),
+            env!("CARGO_PKG_DESCRIPTION"),
+        )
+    }
+
+    /// Override the app info used by help and the other built-ins
+    pub fn set(info: AppInfo) {
+        *APP_INFO.write().unwrap_or_else(|e| e.into_inner()) = Some(info);
+    }
+
+    /// The app info set with `AppInfo::set`, or the package info
+    pub fn current() -> AppInfo {
+        APP_INFO
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+            .unwrap_or_else(Self::from_package)
+    }
+
+    /// Authors joined for display, if there are any
+    pub fn authors_display(&self) -> Option<String> {
+        if self.authors.is_empty() {
+            None
+        } else {
+            Some(self.authors.join(", "))
+        }
+    }
+}
+
+/// `AppInfo` built from the calling crate's `Cargo.toml`
+#[macro_export]
+macro_rules! app_info {
+    () => {
+        $crate::modules::termenu::AppInfo::new(
+            env!("CARGO_PKG_NAME"),
+            env!("CARGO_PKG_VERSION"),
+            env!("CARGO_PKG_AUTHORS"),
+            env!("CARGO_PKG_DESCRIPTION"),
+        )
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_authors_are_split_and_may_be_empty() {
+        let info = AppInfo::new("demo", "1.2.3", "Ann <a@x.io>:Bob", "A demo");
+        assert_eq!(info.authors, vec!["Ann <a@x.io>", "Bob"]);
+        assert_eq!(info.authors_display().unwrap(), "Ann <a@x.io>, Bob");
+
+        let info = AppInfo::new("demo", "1.2.3", "", "A demo");
+        assert!(info.authors.is_empty());
+        assert!(info.authors_display().is_none());
+    }
+}
@@ -0,0 +1,884 @@
+use colored::Colorize;
+use serde_json::json;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use super::app_info::AppInfo;
+use super::context::Context;
+use super::core::{Registry, Termenu};
+use super::exceptions::TermenuError;
+use super::globals::{GLOBAL_FLAGS, GLOBAL_OPTIONS};
+use super::option::{Candidate, CommandOption, OptionKind};
+
+pub const SHELLS: &[&str] = &["bash", "zsh", "fish", "powershell"];
+
+/// Option data the script generators need
+struct OptionSpec {
+    name: String,
+    description: String,
+    /// Only `--name=<value>` is valid
+    requires_value: bool,
+    values: Vec<String>,
+    /// Values come from a runtime provider via `__complete`
+    dynamic: bool,
+}
+
+impl OptionSpec {
+    fn from_option(option: &CommandOption) -> Self {
+        Self {
+            name: option.name.clone(),
+            description: option.description.clone(),
+            requires_value: matches!(option.kind, OptionKind::Value | OptionKind::Choice(_)),
+            values: option.possible_values().to_vec(),
+            dynamic: option.completer.is_some(),
+        }
+    }
+}
+
+struct CommandSpec {
+    name: String,
+    description: String,
+    options: Vec<OptionSpec>,
+    /// Positional arguments, in order
+    arguments: Vec<OptionSpec>,
+}
+
+/// Everything needed to render a completion script
+struct CompletionSpec {
+    program: String,
+    globals: Vec<OptionSpec>,
+    commands: Vec<CommandSpec>,
+}
+
+impl CompletionSpec {
+    fn new(program: &str, commands: &[Termenu]) -> Self {
+        Self {
+            program: program.to_string(),
+            globals: GLOBAL_OPTIONS
+                .iter()
+                .map(|(name, description)| OptionSpec {
+                    name: name.to_string(),
+                    description: description.to_string(),
+                    requires_value: !GLOBAL_FLAGS.contains(name),
+                    values: Vec::new(),
+                    dynamic: false,
+                })
+                .collect(),
+            commands: commands
+                .iter()
+                .filter(|cmd| cmd.is_listed())
+                .map(|cmd| CommandSpec {
+                    name: cmd.command.clone(),
+                    description: cmd.description.clone(),
+                    options: cmd
+                        .options
+                        .iter()
+                        .filter(|opt| opt.is_listed())
+                        .map(OptionSpec::from_option)
+                        .collect(),
+                    arguments: cmd.arguments.iter().map(OptionSpec::from_option).collect(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Shell-safe function name for the program
+    fn ident(&self) -> String {
+        self.program
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect()
+    }
+}
+
+/// Escape for a single-quoted POSIX shell string
+fn sq(value: &str) -> String {
+    value.replace('\'', "'\\''")
+}
+
+/// Text offered for an option: `--name=` when a value is required
+fn option_word(option: &OptionSpec) -> String {
+    if option.requires_value {
+        format!("{}=", option.name)
+    } else {
+        option.name.clone()
+    }
+}
+
+/// Whether an option or argument has values worth completing
+fn has_values(option: &OptionSpec) -> bool {
+    option.dynamic || !option.values.is_empty()
+}
+
+/// Right-hand side of a bash `values=` assignment
+fn bash_values(option: &OptionSpec, ident: &str) -> String {
+    if option.dynamic {
+        format!("\"$(_{}_dynamic)\"", ident)
+    } else {
+        format!("'{}'", sq(&option.values.join(" ")))
+    }
+}
+
+fn render_bash(spec: &CompletionSpec) -> String {
+    let ident = spec.ident();
+    let command_names: Vec<&str> = spec.commands.iter().map(|c| c.name.as_str()).collect();
+    let global_words: Vec<String> = spec.globals.iter().map(option_word).collect();
+
+    let mut option_cases = String::new();
+    let mut value_cases = String::new();
+    let mut argument_cases = String::new();
+    for cmd in &spec.commands {
+        let words: Vec<String> = cmd.options.iter().map(option_word).collect();
+        option_cases.push_str(&format!(
+            "        '{}') opts='{}' ;;\n",
+            sq(&cmd.name),
+            sq(&words.join(" "))
+        ));
+        for opt in cmd.options.iter().filter(|o| has_values(o)) {
+            value_cases.push_str(&format!(
+                "            '{} {}') values={} ;;\n",
+                sq(&cmd.name),
+                sq(&opt.name),
+                bash_values(opt, &ident)
+            ));
+        }
+        for (index, arg) in cmd.arguments.iter().enumerate() {
+            if has_values(arg) {
+                argument_cases.push_str(&format!(
+                    "            '{} {}') values={} ;;\n",
+                    sq(&cmd.name),
+                    index,
+                    bash_values(arg, &ident)
+                ));
+            }
+        }
+    }
+
+    format!(
+        r#"# bash completion for {program}
+_{ident}_dynamic() {{
+    "${{words[0]}}" __complete -- "${{words[@]:1}}" "$cur" 2>/dev/null | cut -f1
+}}
+
+_{ident}() {{
+    local line="${{COMP_LINE:0:COMP_POINT}}"
+    local -a words
+    read -r -a words <<< "$line"
+    local cur=""
+    if [[ "$line" != *" " ]]; then
+        cur="${{words[${{#words[@]}}-1]}}"
+        unset 'words[${{#words[@]}}-1]'
+    fi
+
+    local cmd="" pos=0 i
+    for ((i=1; i<${{#words[@]}}; i++)); do
+        if [[ -z "$cmd" ]]; then
+            [[ "${{words[i]}}" != --* ]] && cmd="${{words[i]}}"
+        elif [[ "${{words[i]}}" != --* ]]; then
+            pos=$((pos + 1))
+        fi
+    done
+
+    if [[ -z "$cmd" ]]; then
+        COMPREPLY=( $(compgen -W '{commands} {globals}' -- "$cur") )
+        [[ "${{COMPREPLY[0]}}" == *= ]] && compopt -o nospace 2>/dev/null
+        return 0
+    fi
+
+    local values=""
+    if [[ "$cur" == --*=* ]]; then
+        local opt="${{cur%%=*}}" val="${{cur#*=}}"
+        case "$cmd $opt" in
+{value_cases}        esac
+        if [[ "$COMP_WORDBREAKS" == *"="* ]]; then
+            COMPREPLY=( $(compgen -W "$values" -- "$val") )
+        else
+            COMPREPLY=( $(compgen -P "$opt=" -W "$values" -- "$val") )
+        fi
+        return 0
+    fi
+
+    if [[ "$cur" != --* ]]; then
+        case "$cmd $pos" in
+{argument_cases}        esac
+        if [[ -n "$values" ]]; then
+            COMPREPLY=( $(compgen -W "$values" -- "$cur") )
+            return 0
+        fi
+    fi
+
+    local opts=""
+    case "$cmd" in
+{option_cases}    esac
+    COMPREPLY=( $(compgen -W "$opts" -- "$cur") )
+    [[ "${{COMPREPLY[0]}}" == *= ]] && compopt -o nospace 2>/dev/null
+    return 0
+}}
+complete -F _{ident} {program}
+"#,
+        program = spec.program,
+        ident = ident,
+        commands = sq(&command_names.join(" ")),
+        globals = sq(&global_words.join(" ")),
+        value_cases = value_cases,
+        argument_cases = argument_cases,
+        option_cases = option_cases,
+    )
+}
+
+/// Escape a `name:description` entry for zsh `_describe`
+fn zsh_entry(name: &str, description: &str) -> String {
+    format!(
+        "'{}:{}'",
+        sq(&name.replace(':', "\\:")),
+        sq(&description.replace('\n', " "))
+    )
+}
+
+/// zsh `case` body filling `vals`, or flagging a runtime lookup
+fn zsh_values(option: &OptionSpec) -> String {
+    if option.dynamic {
+        "dyn=1".to_string()
+    } else {
+        format!(
+            "vals=({})",
+            option
+                .values
+                .iter()
+                .map(|v| format!("'{}'", sq(v)))
+                .collect::<Vec<_>>()
+                .join(" ")
+        )
+    }
+}
+
+fn render_zsh(spec: &CompletionSpec) -> String {
+    let ident = spec.ident();
+    let commands: Vec<String> = spec
+        .commands
+        .iter()
+        .map(|c| zsh_entry(&c.name, &c.description))
+        .collect();
+    let globals_flags: Vec<String> = spec
+        .globals
+        .iter()
+        .filter(|o| !o.requires_value)
+        .map(|o| zsh_entry(&o.name, &o.description))
+        .collect();
+    let globals_values: Vec<String> = spec
+        .globals
+        .iter()
+        .filter(|o| o.requires_value)
+        .map(|o| zsh_entry(&option_word(o), &o.description))
+        .collect();
+
+    let mut option_cases = String::new();
+    let mut value_cases = String::new();
+    let mut argument_cases = String::new();
+    for cmd in &spec.commands {
+        let flags: Vec<String> = cmd
+            .options
+            .iter()
+            .filter(|o| !o.requires_value)
+            .map(|o| zsh_entry(&o.name, &o.description))
+            .collect();
+        let values: Vec<String> = cmd
+            .options
+            .iter()
+            .filter(|o| o.requires_value)
+            .map(|o| zsh_entry(&option_word(o), &o.description))
+            .collect();
+        option_cases.push_str(&format!(
+            "    '{}') flags=({}); valued=({}) ;;\n",
+            sq(&cmd.name),
+            flags.join(" "),
+            values.join(" ")
+        ));
+        for opt in cmd.options.iter().filter(|o| has_values(o)) {
+            value_cases.push_str(&format!(
+                "      '{} {}') {} ;;\n",
+                sq(&cmd.name),
+                sq(&opt.name),
+                zsh_values(opt)
+            ));
+        }
+        for (index, arg) in cmd.arguments.iter().enumerate() {
+            if has_values(arg) {
+                argument_cases.push_str(&format!(
+                    "      '{} {}') {} ;;\n",
+                    sq(&cmd.name),
+                    index,
+                    zsh_values(arg)
+                ));
+            }
+        }
+    }
+
+    format!(
+        r#"#compdef {program}
+# zsh completion for {program}
+_{ident}_dynamic() {{
+  local -a descs
+  descs=("${{(@f)$(${{words[1]}} __complete -- ${{words[2,CURRENT]}} 2>/dev/null)}}")
+  descs=(${{descs:#}})
+  descs=("${{(@)descs//:/\\:}}")
+  descs=("${{(@)descs/$'\t'/:}}")
+  _describe -t values 'value' descs
+}}
+
+_{ident}() {{
+  local -a commands global_flags global_valued flags valued vals
+  local dyn=0
+  commands=({commands})
+  global_flags=({globals_flags})
+  global_valued=({globals_values})
+
+  local cmd="" pos=0 i
+  for ((i=2; i<CURRENT; i++)); do
+    if [[ -z $cmd ]]; then
+      [[ ${{words[i]}} != --* ]] && cmd=${{words[i]}}
+    elif [[ ${{words[i]}} != --* ]]; then
+      (( pos++ ))
+    fi
+  done
+
+  if [[ -z $cmd ]]; then
+    _describe -t commands 'command' commands
+    _describe -t global-options 'global option' global_flags
+    _describe -t global-options 'global option' global_valued -S ''
+    return
+  fi
+
+  local cur=${{words[CURRENT]}}
+  if [[ $cur == --*=* ]]; then
+    local opt=${{cur%%=*}}
+    case "$cmd $opt" in
+{value_cases}    esac
+    compset -P '*='
+    if (( dyn )); then
+      _{ident}_dynamic
+    else
+      compadd -a vals
+    fi
+    return
+  fi
+
+  if [[ $cur != --* ]]; then
+    case "$cmd $pos" in
+{argument_cases}    esac
+    if (( dyn )); then
+      _{ident}_dynamic
+      return
+    elif (( ${{#vals}} )); then
+      compadd -a vals
+      return
+    fi
+  fi
+
+  case $cmd in
+{option_cases}  esac
+  _describe -t options 'option' flags
+  _describe -t options 'option' valued -S ''
+}}
+
+if [[ $zsh_eval_context[-1] == loadautofunc ]]; then
+  _{ident} "$@"
+else
+  compdef _{ident} {program}
+fi
+"#,
+        program = spec.program,
+        ident = ident,
+        commands = commands.join(" "),
+        globals_flags = globals_flags.join(" "),
+        globals_values = globals_values.join(" "),
+        value_cases = value_cases,
+        argument_cases = argument_cases,
+        option_cases = option_cases,
+    )
+}
+
+fn render_fish(spec: &CompletionSpec) -> String {
+    let program = &spec.program;
+    let mut out = format!(
+        "# fish completion for {program}\ncomplete -c {program} -f\n",
+        program = program
+    );
+
+    for cmd in &spec.commands {
+        out.push_str(&format!(
+            "complete -c {} -n '__fish_use_subcommand' -a '{}' -d '{}'\n",
+            program,
+            sq(&cmd.name),
+            sq(&cmd.description)
+        ));
+    }
+    for opt in &spec.globals {
+        out.push_str(&format!(
+            "complete -c {} -n '__fish_use_subcommand' -l '{}'{} -d '{}'\n",
+            program,
+            sq(opt.name.trim_start_matches("--")),
+            if opt.requires_value { " -r" } else { "" },
+            sq(&opt.description)
+        ));
+    }
+
+    let dynamic = format!(
+        "({} __complete -- (commandline -opc)[2..-1] (commandline -ct))",
+        program
+    );
+    for cmd in &spec.commands {
+        let condition = format!("__fish_seen_subcommand_from {}", cmd.name);
+        for opt in &cmd.options {
+            let values = if opt.dynamic {
+                format!(" -a '{}'", sq(&dynamic))
+            } else if opt.values.is_empty() {
+                String::new()
+            } else {
+                format!(" -a '{}'", sq(&opt.values.join(" ")))
+            };
+            let mode = if has_values(opt) {
+                " -x"
+            } else if opt.requires_value {
+                " -r"
+            } else {
+                ""
+            };
+            out.push_str(&format!(
+                "complete -c {} -n '{}' -l '{}'{}{} -d '{}'\n",
+                program,
+                condition,
+                sq(opt.name.trim_start_matches("--")),
+                mode,
+                values,
+                sq(&opt.description)
+            ));
+        }
+        // fish has no positional index helper; `__complete` tracks positions itself
+        let values = if cmd.arguments.iter().any(|a| a.dynamic) {
+            dynamic.clone()
+        } else {
+            cmd.arguments
+                .iter()
+                .flat_map(|a| a.values.iter().cloned())
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        if !values.is_empty() {
+            out.push_str(&format!(
+                "complete -c {} -n '{}' -a '{}'\n",
+                program,
+                condition,
+                sq(&values)
+            ));
+        }
+    }
+
+    out
+}
+
+/// Escape for a single-quoted PowerShell string
+fn ps(value: &str) -> String {
+    value.replace('\'', "''")
+}
+
+fn render_powershell(spec: &CompletionSpec) -> String {
+    let mut commands = String::new();
+    let mut options = String::new();
+    let mut values = String::new();
+    let mut arguments = String::new();
+    let mut dynamic: Vec<String> = Vec::new();
+
+    for cmd in &spec.commands {
+        commands.push_str(&format!(
+            "        '{}' = '{}'\n",
+            ps(&cmd.name),
+            ps(&cmd.description)
+        ));
+        let entries: Vec<String> = cmd
+            .options
+            .iter()
+            .map(|o| format!("@('{}', '{}')", ps(&option_word(o)), ps(&o.description)))
+            .collect();
+        options.push_str(&format!(
+            "        '{}' = @({})\n",
+            ps(&cmd.name),
+            entries.join(", ")
+        ));
+        for opt in cmd.options.iter().filter(|o| o.dynamic) {
+            dynamic.push(format!("'{} {}'", ps(&cmd.name), ps(&opt.name)));
+        }
+        for (index, arg) in cmd.arguments.iter().enumerate() {
+            if arg.dynamic {
+                dynamic.push(format!("'{} {}'", ps(&cmd.name), index));
+            }
+        }
+        for opt in cmd.options.iter().filter(|o| !o.values.is_empty()) {
+            values.push_str(&format!(
+                "        '{} {}' = @({})\n",
+                ps(&cmd.name),
+                ps(&opt.name),
+                opt.values
+                    .iter()
+                    .map(|v| format!("'{}'", ps(v)))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+        for (index, arg) in cmd.arguments.iter().enumerate() {
+            if !arg.values.is_empty() {
+                arguments.push_str(&format!(
+                    "        '{} {}' = @({})\n",
+                    ps(&cmd.name),
+                    index,
+                    arg.values
+                        .iter()
+                        .map(|v| format!("'{}'", ps(v)))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ));
+            }
+        }
+    }
+    let globals: Vec<String> = spec
+        .globals
+        .iter()
+        .map(|o| format!("@('{}', '{}')", ps(&option_word(o)), ps(&o.description)))
+        .collect();
+
+    format!(
+        r#"# PowerShell completion for {program}
+Register-ArgumentCompleter -Native -CommandName '{program}' -ScriptBlock {{
+    param($wordToComplete, $commandAst, $cursorPosition)
+
+    $commands = @{{
+{commands}    }}
+    $globals = @({globals})
+    $options = @{{
+{options}    }}
+    $values = @{{
+{values}    }}
+    $arguments = @{{
+{arguments}    }}
+    $dynamic = @({dynamic})
+
+    $words = @($commandAst.CommandElements | Select-Object -Skip 1 | ForEach-Object {{ $_.ToString() }})
+    if ($wordToComplete -ne '' -and $words.Count -gt 0) {{
+        $words = @($words | Select-Object -First ($words.Count - 1))
+    }}
+    $cmd = $null
+    $pos = 0
+    foreach ($word in $words) {{
+        if ($word.StartsWith('--')) {{ continue }}
+        if ($null -eq $cmd) {{ $cmd = $word }} else {{ $pos++ }}
+    }}
+
+    $result = [System.Collections.Generic.List[System.Management.Automation.CompletionResult]]::new()
+    function Add-Result($text, $tip) {{
+        if (-not $tip) {{ $tip = $text }}
+        $result.Add([System.Management.Automation.CompletionResult]::new($text, $text, 'ParameterValue', $tip))
+    }}
+    function Get-Dynamic {{
+        $program = $commandAst.CommandElements[0].ToString()
+        & $program __complete -- @words $wordToComplete 2>$null | ForEach-Object {{
+            $parts = $_ -split "`t", 2
+            ,@($parts[0], $parts[1])
+        }}
+    }}
+
+    if ($null -eq $cmd) {{
+        foreach ($name in $commands.Keys) {{
+            if ($name -like "$wordToComplete*") {{ Add-Result $name $commands[$name] }}
+        }}
+        foreach ($opt in $globals) {{
+            if ($opt[0] -like "$wordToComplete*") {{ Add-Result $opt[0] $opt[1] }}
+        }}
+    }} elseif ($wordToComplete -match '^(--[^=]+)=(.*)$') {{
+        $key = "$cmd $($Matches[1])"
+        $prefix = $Matches[1]
+        $partial = $Matches[2]
+        if ($dynamic -contains $key) {{
+            foreach ($item in Get-Dynamic) {{ Add-Result "$prefix=$($item[0])" $item[1] }}
+        }} elseif ($values.ContainsKey($key)) {{
+            foreach ($value in $values[$key]) {{
+                if ($value -like "$partial*") {{ Add-Result "$prefix=$value" $value }}
+            }}
+        }}
+    }} elseif (-not $wordToComplete.StartsWith('--') -and $dynamic -contains "$cmd $pos") {{
+        foreach ($item in Get-Dynamic) {{ Add-Result $item[0] $item[1] }}
+    }} elseif (-not $wordToComplete.StartsWith('--') -and $arguments.ContainsKey("$cmd $pos")) {{
+        foreach ($value in $arguments["$cmd $pos"]) {{
+            if ($value -like "$wordToComplete*") {{ Add-Result $value $value }}
+        }}
+    }} elseif ($options.ContainsKey($cmd)) {{
+        foreach ($opt in $options[$cmd]) {{
+            if ($opt[0] -like "$wordToComplete*") {{ Add-Result $opt[0] $opt[1] }}
+        }}
+    }}
+    $result
+}}
+"#,
+        program = ps(&spec.program),
+        commands = commands,
+        globals = globals.join(", "),
+        options = options,
+        values = values,
+        arguments = arguments,
+        dynamic = dynamic.join(", "),
+    )
+}
+
+/// Resolve candidates for a partial command line (the `__complete` protocol)
+///
+/// `words` are the arguments after the program name; the last one is the word
+/// being completed and may be empty. Option values are returned without the
+/// `--name=` prefix.
+pub fn complete(commands: &[Termenu], words: &[String]) -> Vec<Candidate> {
+    let (cur, prior) = match words.split_last() {
+        Some((cur, prior)) => (cur.as_str(), prior),
+        None => ("", words),
+    };
+
+    let mut command: Option<&Termenu> = None;
+    let mut position = 0;
+    for word in prior.iter().filter(|w| !w.starts_with("--")) {
+        match command {
+            None => match commands.iter().find(|c| c.is_named(word)) {
+                Some(found) => command = Some(found),
+                None => return Vec::new(),
+            },
+            Some(_) => position += 1,
+        }
+    }
+
+    let option_candidate = |name: &str, description: &str, requires_value: bool| {
+        let value = if requires_value {
+            format!("{}=", name)
+        } else {
+            name.to_string()
+        };
+        Candidate::with_description(&value, description)
+    };
+
+    let mut candidates: Vec<Candidate> = match command {
+        None if cur.starts_with("--") => GLOBAL_OPTIONS
+            .iter()
+            .map(|(name, desc)| option_candidate(name, desc, !GLOBAL_FLAGS.contains(name)))
+            .collect(),
+        None => commands
+            .iter()
+            .filter(|c| c.is_listed())
+            .map(|c| Candidate::with_description(&c.command, &c.description))
+            .collect(),
+        Some(cmd) => {
+            if let Some((key, partial)) = cur.split_once('=') {
+                return cmd
+                    .find_option(key)
+                    .map(|opt| opt.complete(partial))
+                    .unwrap_or_default();
+            }
+            if !cur.starts_with("--")
+                && let Some(argument) = cmd.arguments.get(position)
+            {
+                return argument.complete(cur);
+            }
+            cmd.options
+                .iter()
+                .filter(|o| o.is_listed())
+                .map(|o| {
+                    option_candidate(
+                        &o.name,
+                        &o.description,
+                        matches!(o.kind, OptionKind::Value | OptionKind::Choice(_)),
+                    )
+                })
+                .collect()
+        }
+    };
+
+    candidates.retain(|c| c.value.starts_with(cur));
+    candidates
+}
+
+/// Generate a completion script for `shell` from the registered commands
+pub fn generate(shell: &str, program: &str, commands: &[Termenu]) -> Result<String, TermenuError> {
+    let spec = CompletionSpec::new(program, commands);
+    match shell {
+        "bash" => Ok(render_bash(&spec)),
+        "zsh" => Ok(render_zsh(&spec)),
+        "fish" => Ok(render_fish(&spec)),
+        "powershell" => Ok(render_powershell(&spec)),
+        other => Err(TermenuError::input_unknown_error(Some(json!({
+            "issue": format!("Unsupported shell '{}'. Use one of: {}", other, SHELLS.join(", "))
+        })))),
+    }
+}
+
+fn home_dir() -> Option<PathBuf> {
+    env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .map(PathBuf::from)
+}
+
+/// Standard per-user location for a shell's completion script
+pub fn install_path(shell: &str, program: &str) -> Option<PathBuf> {
+    let home = home_dir()?;
+    let data_home = env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| home.join(".local").join("share"));
+    let config_home = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| home.join(".config"));
+
+    match shell {
+        "bash" => Some(
+            data_home
+                .join("bash-completion")
+                .join("completions")
+                .join(program),
+        ),
+        "zsh" => Some(home.join(".zfunc").join(format!("_{}", program))),
+        "fish" => Some(
+            config_home
+                .join("fish")
+                .join("completions")
+                .join(format!("{}.fish", program)),
+        ),
+        "powershell" => Some(
+            config_home
+                .join("powershell")
+                .join("completions")
+                .join(format!("{}.ps1", program)),
+        ),
+        _ => None,
+    }
+}
+
+fn install(shell: &str, program: &str, script: &str) -> Result<PathBuf, TermenuError> {
+    let path = install_path(shell, program).ok_or_else(|| {
+        TermenuError::framework_resource_error(Some(json!({
+            "issue": "Cannot determine the home directory to install completions into."
+        })))
+    })?;
+    let io_error = |e: std::io::Error| {
+        TermenuError::framework_resource_error(Some(json!({
+            "issue": format!("Failed to write {}: {}", path.display(), e)
+        })))
+    };
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(io_error)?;
+    }
+    fs::write(&path, script).map_err(io_error)?;
+    Ok(path)
+}
+
+/// Built-in `completions` command
+pub fn register() -> Termenu {
+    let mut command = Termenu::new_command_with_context(
+        "completions",
+        "Generate or install shell completion scripts.",
+        |ctx: &Context| {
+            let registry = ctx.state::<Registry>()?;
+            let program = AppInfo::current().name;
+            let shell = ctx.option("--shell").ok_or_else(|| {
+                TermenuError::input_missing_error(Some(json!({
+                    "issue": format!("Shell is required. Use `--shell=<{}>`.", SHELLS.join("|"))
+                })))
+            })?;
+            let script = generate(shell, &program, registry.commands())?;
+
+            if !ctx.has_option("--install") {
+                print!("{}", script);
+                return Ok(());
+            }
+
+            let path = install(shell, &program, &script)?;
+            println!(
+                "{} Installed {} completions to {}",
+                "✔".green(),
+                shell,
+                path.display()
+            );
+            match shell {
+                "zsh" => println!(
+                    "  Make sure your ~/.zshrc has `fpath=(~/.zfunc $fpath)` before `compinit`."
+                ),
+                "powershell" => println!(
+                    "  Add `. \"{}\"` to your $PROFILE to load it.",
+                    path.display()
+                ),
+                _ => println!("  Open a new shell to start using it."),
+            }
+            Ok(())
+        },
+    );
+
+    command
+        .add_option("--shell", "Target shell: bash, zsh, fish or powershell.")
+        .choices(SHELLS);
+    command
+        .add_option(
+            "--install",
+            "Write the script to the shell's user completion directory.",
+        )
+        .flag();
+
+    command
+        .add_example(
+            "completions --shell=bash --install",
+            "Install bash completions for the current user.",
+        )
+        .add_example(
+            "completions --shell=zsh > ~/.zfunc/_termenu",
+            "Write the zsh script to a file of your choice.",
+        )
+        .see_also(&["man", "docs"])
+        .error_codes(&["E112", "E111", "E013"]);
+
+    command
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Vec<Termenu> {
+        let mut cmd = Termenu::new_command("deploy", "Ship it.", |_| Ok(()));
+        cmd.add_option("--env", "Target environment.")
+            .choices(&["staging", "prod"]);
+        cmd.add_option("--dry-run", "Only print the plan.").flag();
+        cmd.add_argument("profile", "Config profile.")
+            .complete_with(|_| vec![Candidate::with_description("default", "Local profile")]);
+        vec![cmd]
+    }
+
+    #[test]
+    fn test_scripts_include_commands_and_choices() {
+        for shell in SHELLS {
+            let script = generate(shell, "demo", &sample()).unwrap();
+            assert!(script.contains("deploy"), "{} script lacks command", shell);
+            assert!(script.contains("staging"), "{} script lacks choices", shell);
+            assert!(script.contains("dry-run"), "{} script lacks flag", shell);
+        }
+        assert!(generate("tcsh", "demo", &sample()).is_err());
+    }
+
+    #[test]
+    fn test_complete_runs_providers_by_position() {
+        let words = |w: &[&str]| w.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        let commands = sample();
+
+        let found = complete(&commands, &words(&["deploy", "de"]));
+        assert_eq!(
+            found,
+            vec![Candidate::with_description("default", "Local profile")]
+        );
+
+        let found = complete(&commands, &words(&["deploy", "--env=st"]));
+        assert_eq!(found, vec![Candidate::new("staging")]);
+
+        let found = complete(&commands, &words(&["dep"]));
+        assert_eq!(found[0].value, "deploy");
+    }
+}
@@ -0,0 +1,47 @@
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use super::exceptions::TermenuError;
+use super::state::State;
+
+/// Everything a handler receives for a single invocation
+#[derive(Clone)]
+pub struct Context {
+    options: HashMap<String, Option<String>>,
+    state: Arc<State>,
+}
+
+impl Context {
+    pub fn new(options: HashMap<String, Option<String>>, state: Arc<State>) -> Self {
+        Self { options, state }
+    }
+
+    /// Parsed options passed to the command
+    pub fn options(&self) -> &HashMap<String, Option<String>> {
+        &self.options
+    }
+
+    /// Consume the context and keep only the parsed options
+    pub fn into_options(self) -> HashMap<String, Option<String>> {
+        self.options
+    }
+
+    /// Value of an option given as `--key=value`
+    pub fn option(&self, key: &str) -> Option<&str> {
+        self.options.get(key).and_then(|v| v.as_deref())
+    }
+
+    /// Whether an option was passed at all
+    pub fn has_option(&self, key: &str) -> bool {
+        self.options.contains_key(key)
+    }
+
+    /// Fetch a shared resource registered in `State`
+    pub fn state<T>(&self) -> Result<Arc<T>, TermenuError>
+    where
+        T: Any + Send + Sync,
+    {
+        self.state.get::<T>()
+    }
+}
@@ -1,65 +1,157 @@
 use crate::modules::termenu::Banner;
 use colored::Colorize;
-use serde::Deserialize;
 use serde_json::json;
 use std::collections::{HashMap, HashSet};
 use std::env;
-use std::fs;
 use std::future::Future;
+use std::io::IsTerminal;
 use std::pin::Pin;
 use std::sync::Arc;
 
+use super::app_info::AppInfo;
+use super::completions;
+use super::context::Context;
+use super::crash;
+use super::deprecation::Deprecation;
+use super::docs;
 use super::exceptions::TermenuError;
+use super::explain;
+use super::globals::{GLOBAL_OPTIONS, GlobalOptions};
+use super::jobs::{self, JOB_ID_ENV};
+use super::layout::{self, Columns};
+use super::manpage;
+use super::option::{Candidate, CommandOption};
+use super::report;
+use super::schedule::run_scheduled;
+use super::schema::Schema;
+use super::state::State;
+use super::version::{self, BuildInfo};
+use super::watch::watch;
 
 /// Constants
 pub const MAX_COMMAND: i32 = 100;
 pub const MIN_COMMAND: i32 = 0;
+/// Help heading for the commands Termenu adds itself
+pub const BUILTIN_CATEGORY: &str = "Built-in";
+/// Names of the commands Termenu adds itself, which app commands may not use
+pub const BUILTIN_COMMANDS: &[&str] = &[
+    "jobs",
+    "version",
+    "completions",
+    "man",
+    "docs",
+    "explain",
+    "help",
+    // Entry point of the completion scripts
+    "__complete",
+];
 
-#[derive(Debug, Deserialize)]
-struct CargoToml {
-    package: Package,
-    dependencies: Option<toml::value::Table>,
+/// Help asked for on the command line instead of running a command
+enum HelpRequest<'a> {
+    /// The overview of every command
+    General,
+    /// The page of a single command
+    Command(&'a Termenu),
 }
 
-#[derive(Debug, Deserialize)]
-struct Package {
-    name: String,
-    version: String,
-    authors: Vec<String>,
+/// A runnable usage example, written without the program name
+#[derive(Debug, Clone, PartialEq)]
+pub struct Example {
+    pub command: String,
+    pub description: String,
 }
 
 #[derive(Clone)]
 pub struct Termenu {
     pub command: String,
     pub description: String,
-    pub options: Vec<(String, String)>,
+    /// Extra paragraphs shown in per-command help and generated docs
+    pub long_description: Option<String>,
+    pub examples: Vec<Example>,
+    /// Names of related commands
+    pub see_also: Vec<String>,
+    /// Help heading the command is listed under
+    pub category: Option<String>,
+    /// Still runnable, but left out of help, docs and completions
+    pub hidden: bool,
+    /// Also left out of generated docs and man pages; only `hidden()` sets it, so the
+    /// reference doesn't change with build-time conditions given to `hidden_if`
+    pub hidden_from_docs: bool,
+    /// Other names the command can be run by
+    pub aliases: Vec<String>,
+    /// Set when the command itself is on its way out
+    pub deprecation: Option<Deprecation>,
+    /// Old names that still run this command, with a warning
+    pub renamed_from: Vec<(String, Deprecation)>,
+    /// Error codes the handler may return, besides the ones from option parsing
+    pub error_codes: Vec<String>,
+    pub options: Vec<CommandOption>,
+    pub arguments: Vec<CommandOption>,
     #[allow(clippy::type_complexity)]
-    pub handler: Option<
-        Arc<dyn Fn(&HashMap<String, Option<String>>) -> Result<(), TermenuError> + Send + Sync>,
-    >,
+    pub handler: Option<Arc<dyn Fn(&Context) -> Result<(), TermenuError> + Send + Sync>>,
     #[allow(clippy::type_complexity)]
     pub async_handler: Option<
         Arc<
-            dyn Fn(
-                    HashMap<String, Option<String>>,
-                )
-                    -> Pin<Box<dyn Future<Output = Result<(), TermenuError>> + Send>>
+            dyn Fn(Context) -> Pin<Box<dyn Future<Output = Result<(), TermenuError>> + Send>>
                 + Send
                 + Sync,
         >,
     >,
 }
 
+/// Every command known to the processor, built-ins included
+///
+/// Registered in `State` so built-ins can inspect the command set via `ctx.state::<Registry>()`.
+pub struct Registry {
+    commands: Vec<Termenu>,
+}
+
+impl Registry {
+    pub fn new(commands: Vec<Termenu>) -> Self {
+        Self { commands }
+    }
+
+    pub fn commands(&self) -> &[Termenu] {
+        &self.commands
+    }
+
+    /// Find a command by name or alias
+    pub fn find(&self, name: &str) -> Option<&Termenu> {
+        self.commands.iter().find(|cmd| cmd.is_named(name))
+    }
+}
+
 impl Termenu {
     /// Create a new synchronous command with a handler
     pub fn new_command<F>(command: &str, description: &str, handler: F) -> Self
     where
         F: Fn(&HashMap<String, Option<String>>) -> Result<(), TermenuError> + Send + Sync + 'static,
+    {
+        Self::new_command_with_context(command, description, move |ctx: &Context| {
+            handler(ctx.options())
+        })
+    }
+
+    /// Create a new synchronous command whose handler receives the full `Context`
+    pub fn new_command_with_context<F>(command: &str, description: &str, handler: F) -> Self
+    where
+        F: Fn(&Context) -> Result<(), TermenuError> + Send + Sync + 'static,
     {
         Self {
             command: command.to_string(),
             description: description.to_string(),
+            long_description: None,
+            examples: Vec::new(),
+            see_also: Vec::new(),
+            category: None,
+            hidden: false,
+            hidden_from_docs: false,
+            aliases: Vec::new(),
+            deprecation: None,
+            renamed_from: Vec::new(),
+            error_codes: Vec::new(),
             options: Vec::new(),
+            arguments: Vec::new(),
             handler: Some(Arc::new(handler)),
             async_handler: None,
         }
@@ -70,20 +162,154 @@ impl Termenu {
     where
         F: Fn(HashMap<String, Option<String>>) -> Fut + Send + Sync + 'static,
         Fut: Future<Output = Result<(), TermenuError>> + Send + 'static,
+    {
+        Self::new_async_command_with_context(command, description, move |ctx: Context| {
+            handler(ctx.into_options())
+        })
+    }
+
+    /// Create a new asynchronous command whose handler receives the full `Context`
+    pub fn new_async_command_with_context<F, Fut>(
+        command: &str,
+        description: &str,
+        handler: F,
+    ) -> Self
+    where
+        F: Fn(Context) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), TermenuError>> + Send + 'static,
     {
         Self {
             command: command.to_string(),
             description: description.to_string(),
+            long_description: None,
+            examples: Vec::new(),
+            see_also: Vec::new(),
+            category: None,
+            hidden: false,
+            hidden_from_docs: false,
+            aliases: Vec::new(),
+            deprecation: None,
+            renamed_from: Vec::new(),
+            error_codes: Vec::new(),
             options: Vec::new(),
+            arguments: Vec::new(),
             handler: None,
-            async_handler: Some(Arc::new(move |opts| Box::pin(handler(opts)))),
+            async_handler: Some(Arc::new(move |ctx| Box::pin(handler(ctx)))),
+        }
+    }
+
+    /// Set the detailed description shown below the one-line summary
+    pub fn long_description(&mut self, text: &str) -> &mut Self {
+        self.long_description = Some(text.to_string());
+        self
+    }
+
+    /// Add a usage example, e.g. `add_example("docs --check --output=README.md", "Fail if stale.")`
+    pub fn add_example(&mut self, command: &str, description: &str) -> &mut Self {
+        self.examples.push(Example {
+            command: command.to_string(),
+            description: description.to_string(),
+        });
+        self
+    }
+
+    /// Link related commands by name
+    pub fn see_also(&mut self, commands: &[&str]) -> &mut Self {
+        self.see_also
+            .extend(commands.iter().map(|name| name.to_string()));
+        self
+    }
+
+    /// List the command under its own heading in help
+    pub fn category(&mut self, name: &str) -> &mut Self {
+        self.category = Some(name.to_string());
+        self
+    }
+
+    /// Leave the command out of listings
+    pub fn hidden(&mut self) -> &mut Self {
+        self.hidden_if(true);
+        self.hidden_from_docs = true;
+        self
+    }
+
+    /// Hide the command from help and completions when `condition` holds, e.g.
+    /// `hidden_if(!cfg!(debug_assertions))`; generated docs still list it
+    ///
+    /// Replaces an earlier `hidden()` or `hidden_if`, whichever was called last wins.
+    pub fn hidden_if(&mut self, condition: bool) -> &mut Self {
+        self.hidden = condition;
+        self.hidden_from_docs = false;
+        self
+    }
+
+    /// Let the command also run as `name`
+    pub fn alias(&mut self, name: &str) -> &mut Self {
+        self.aliases.push(name.to_string());
+        self
+    }
+
+    /// Whether `name` is the command name, an alias or a deprecated old name
+    pub fn is_named(&self, name: &str) -> bool {
+        self.command == name
+            || self.aliases.iter().any(|alias| alias == name)
+            || self.renamed_from.iter().any(|(old, _)| old == name)
+    }
+
+    /// Keep the command working with a warning, and leave it out of default help
+    pub fn deprecated(&mut self, replacement: Option<&str>, removal: Option<&str>) -> &mut Self {
+        self.deprecation = Some(Deprecation::new(replacement, removal));
+        self
+    }
+
+    /// Forward the old name `old` to this command, with a deprecation warning
+    pub fn renamed_from(&mut self, old: &str, removal: Option<&str>) -> &mut Self {
+        let deprecation = Deprecation::new(Some(&self.command), removal);
+        self.renamed_from.push((old.to_string(), deprecation));
+        self
+    }
+
+    /// Whether the command shows up in default help and completions
+    pub fn is_listed(&self) -> bool {
+        !self.hidden && self.deprecation.is_none()
+    }
+
+    /// Print the deprecation warnings that apply when the command is invoked as `name`
+    pub fn warn_if_deprecated(&self, name: &str) {
+        if let Some((_, deprecation)) = self.renamed_from.iter().find(|(old, _)| old == name) {
+            deprecation.warn("Command", name);
         }
+        if let Some(deprecation) = &self.deprecation {
+            deprecation.warn("Command", &self.command);
+        }
+    }
+
+    /// Declare error codes the handler may return, e.g. `error_codes(&["E611"])`
+    pub fn error_codes(&mut self, codes: &[&str]) -> &mut Self {
+        self.error_codes
+            .extend(codes.iter().map(|code| code.to_string()));
+        self
     }
 
     /// Add an option with a description
-    pub fn add_option(&mut self, option: &str, description: &str) {
-        self.options
-            .push((option.to_string(), description.to_string()));
+    ///
+    /// Returns the option so its kind can be narrowed, e.g. `.flag()` or `.choices(..)`.
+    pub fn add_option(&mut self, option: &str, description: &str) -> &mut CommandOption {
+        self.options.push(CommandOption::new(option, description));
+        self.options.last_mut().expect("option was just pushed")
+    }
+
+    /// Add a positional argument with a description
+    ///
+    /// Positional values are stored in the parsed map under `name` (no `--` prefix).
+    pub fn add_argument(&mut self, name: &str, description: &str) -> &mut CommandOption {
+        self.arguments.push(CommandOption::new(name, description));
+        self.arguments.last_mut().expect("argument was just pushed")
+    }
+
+    /// Find a registered option by name, including deprecated old names
+    pub fn find_option(&self, name: &str) -> Option<&CommandOption> {
+        self.options.iter().find(|opt| opt.is_named(name))
     }
 
     /// Parse raw arguments into key/value map
@@ -92,37 +318,117 @@ impl Termenu {
         raw_args: &[String],
     ) -> Result<HashMap<String, Option<String>>, TermenuError> {
         let mut parsed: HashMap<String, Option<String>> = HashMap::new();
+        let mut positional = self.arguments.iter();
 
-        for arg in raw_args {
-            if let Some((key, value)) = arg.split_once('=') {
-                if self.options.iter().any(|(opt, _)| opt == key) {
-                    parsed.insert(key.to_string(), Some(value.to_string()));
-                } else {
-                    return Err(TermenuError::invalid_command_error(Some(json!({
-                        "issue": format!("Unknown option: '{}'", key)
-                    }))));
+        // Errors point at the argument's index in `raw_args`; the processor shifts it
+        for (index, arg) in raw_args.iter().enumerate() {
+            if !arg.starts_with("--") {
+                match positional.next() {
+                    Some(argument) => {
+                        argument
+                            .check(Some(arg))
+                            .map_err(|e| e.at_argument(index))?;
+                        parsed.insert(argument.name.clone(), Some(arg.clone()));
+                    }
+                    None => {
+                        return Err(TermenuError::invalid_command_error(Some(json!({
+                            "issue": format!("Unexpected argument: '{}'", arg)
+                        })))
+                        .at_argument(index)
+                        .with_hint(&self.arguments_hint()));
+                    }
                 }
-            } else if self.options.iter().any(|(opt, _)| opt == arg) {
-                parsed.insert(arg.clone(), None);
-            } else {
+                continue;
+            }
+
+            let (key, value) = match arg.split_once('=') {
+                Some((key, value)) => (key, Some(value)),
+                None => (arg.as_str(), None),
+            };
+            let Some(option) = self.find_option(key) else {
                 return Err(TermenuError::invalid_command_error(Some(json!({
-                    "issue": format!("Unknown option: '{}'", arg)
-                }))));
+                    "issue": format!("Unknown option: '{}'", key)
+                })))
+                .at_argument(index)
+                .with_hint(&self.unknown_option_hint(key)));
+            };
+            option.check(value).map_err(|e| e.at_argument(index))?;
+            option.warn_if_deprecated(key);
+            // Stored under the current name so handlers never see old names
+            parsed.insert(option.name.clone(), value.map(str::to_string));
+        }
+
+        for option in self.options.iter().chain(&self.arguments) {
+            if option.required && !parsed.contains_key(&option.name) {
+                let hint = if option.name.starts_with("--") {
+                    format!("Pass it as {}=<value>.", option.name)
+                } else {
+                    format!("Give <{}> after '{}'.", option.name, self.command)
+                };
+                return Err(TermenuError::input_missing_error(Some(json!({
+                    "issue": format!("'{}' is required", option.name)
+                })))
+                .with_hint(&hint));
+            }
+            if let Some(default) = &option.default {
+                parsed
+                    .entry(option.name.clone())
+                    .or_insert_with(|| Some(default.clone()));
             }
         }
 
         Ok(parsed)
     }
 
+    /// Hint for an extra positional argument: what the command does take
+    fn arguments_hint(&self) -> String {
+        if self.arguments.is_empty() {
+            return format!(
+                "'{}' takes no arguments; options are written as --name.",
+                self.command
+            );
+        }
+        let names: Vec<String> = self
+            .arguments
+            .iter()
+            .map(|argument| format!("<{}>", argument.name))
+            .collect();
+        format!("'{}' takes only {}.", self.command, names.join(" "))
+    }
+
+    /// Hint for an unknown option: the closest listed option, else where to find them
+    fn unknown_option_hint(&self, key: &str) -> String {
+        let names = self
+            .options
+            .iter()
+            .filter(|option| option.is_listed())
+            .map(|option| option.name.as_str())
+            .chain(["--help"]);
+        match report::closest(key, names) {
+            Some(name) => format!("Did you mean '{}'?", name),
+            None => format!(
+                "Run '{} help {}' to see its options.",
+                AppInfo::current().name,
+                self.command
+            ),
+        }
+    }
+
     /// Execute either sync or async handler automatically
     pub async fn execute(
         &self,
         options: HashMap<String, Option<String>>,
     ) -> Result<(), TermenuError> {
+        self.execute_with_context(Context::new(options, Arc::new(State::new())))
+            .await
+    }
+
+    /// Execute the handler with a prepared `Context`
+    pub async fn execute_with_context(&self, ctx: Context) -> Result<(), TermenuError> {
         if let Some(handler) = &self.handler {
-            handler(&options)
+            handler(&ctx)
         } else if let Some(async_handler) = &self.async_handler {
-            async_handler(options).await
+            async_handler(ctx).await
         } else {
             Err(TermenuError::invalid_command_error(Some(json!({
                 "issue": "No handler found for this command."
@@ -130,163 +436,729 @@ impl Termenu {
         }
     }
 
-    /// Validate a list of registered commands
-    pub fn validate_commands(commands: Vec<Termenu>) -> Vec<Termenu> {
+    /// Every problem with a list of app commands, empty if there are none
+    pub fn check_commands(commands: &[Termenu]) -> Vec<String> {
+        let mut problems = Vec::new();
+
         if commands.len() as i32 > MAX_COMMAND {
-            panic!(
-                "{} {} (max allowed: {})",
-                "Too many commands registered:".red().bold(),
+            problems.push(format!(
+                "Too many commands registered: {} (max allowed: {})",
                 commands.len(),
                 MAX_COMMAND
-            );
+            ));
         }
-
         if commands.len() as i32 <= MIN_COMMAND {
-            panic!(
-                "{} {} (min allowed: {})",
-                "Minimum number of commands not reached:".red().bold(),
+            problems.push(format!(
+                "Minimum number of commands not reached: {} (min allowed: {})",
                 commands.len(),
-                MIN_COMMAND
-            );
+                MIN_COMMAND + 1
+            ));
         }
 
-        let mut seen: HashSet<String> = HashSet::new();
-        for cmd in &commands {
-            if !seen.insert(cmd.command.clone()) {
-                panic!(
-                    "{} '{}' ",
-                    "Duplicate command detected:".red().bold(),
-                    cmd.command
-                );
+        let mut seen: HashSet<&str> = HashSet::new();
+        for cmd in commands {
+            if cmd.command.trim().is_empty() {
+                problems.push("Command name cannot be empty".to_string());
+            } else if cmd.description.trim().is_empty() {
+                problems.push(format!("Command description missing for '{}'", cmd.command));
+            }
+            if cmd.handler.is_none() && cmd.async_handler.is_none() {
+                problems.push(format!("Command '{}' has no handler", cmd.command));
             }
-        }
 
-        for cmd in &commands {
-            if cmd.command.trim().is_empty() {
-                panic!("{}", "Command name cannot be empty".red().bold());
+            let old_names = cmd.renamed_from.iter().map(|(old, _)| old);
+            for name in std::iter::once(&cmd.command)
+                .chain(&cmd.aliases)
+                .chain(old_names)
+            {
+                if name.trim().is_empty() {
+                    // An empty command name is reported above
+                    if name != &cmd.command {
+                        problems.push(format!("Alias of '{}' cannot be empty", cmd.command));
+                    }
+                } else if BUILTIN_COMMANDS.contains(&name.as_str()) {
+                    problems.push(format!(
+                        "Command name '{}' collides with the built-in '{}'",
+                        name, name
+                    ));
+                } else if !seen.insert(name) {
+                    problems.push(format!("Duplicate command detected: '{}'", name));
+                }
             }
 
-            if cmd.description.trim().is_empty() {
-                panic!(
-                    "{} '{}' ",
-                    "Command description missing for".red().bold(),
-                    cmd.command
-                );
+            let mut option_names: HashSet<&str> = HashSet::new();
+            for option in &cmd.options {
+                if !option.name.starts_with("--") || option.name.len() < 3 {
+                    problems.push(format!(
+                        "Option '{}' of '{}' must start with '--'",
+                        option.name, cmd.command
+                    ));
+                }
+            }
+            for argument in &cmd.arguments {
+                let name = &argument.name;
+                if name.is_empty() || name.starts_with('-') || name.contains(char::is_whitespace) {
+                    problems.push(format!(
+                        "Argument '{}' of '{}' must be a plain name like 'file'",
+                        name, cmd.command
+                    ));
+                }
+            }
+            for option in cmd.options.iter().chain(&cmd.arguments) {
+                let old_names = option.renamed_from.iter().map(|(old, _)| old);
+                for name in std::iter::once(&option.name).chain(old_names) {
+                    if name == "--help" {
+                        problems.push(format!(
+                            "Option '--help' of '{}' collides with the built-in '--help'",
+                            cmd.command
+                        ));
+                    } else if !option_names.insert(name) {
+                        problems.push(format!("Duplicate option '{}' in '{}'", name, cmd.command));
+                    }
+                }
             }
         }
 
-        if cfg!(debug_assertions) {
-            println!(
+        problems
+    }
+
+    /// Check the app commands before running them, reporting every problem at once
+    pub fn validate_commands(commands: Vec<Termenu>) -> Result<Vec<Termenu>, TermenuError> {
+        let problems = Self::check_commands(&commands);
+        if !problems.is_empty() {
+            let list: Vec<String> = problems.iter().map(|p| format!("\n  - {}", p)).collect();
+            return Err(TermenuError::framework_unknown_error(Some(json!({
+                "issue": format!(
+                    "{} problem(s) with the registered commands:{}",
+                    problems.len(),
+                    list.concat()
+                ),
+                "problems": problems
+            }))));
+        }
+
+        // Written to stderr so generated output (completions, JSON) stays clean,
+        // and only to terminals so piped stderr carries nothing but errors
+        if cfg!(debug_assertions) && std::io::stderr().is_terminal() {
+            eprintln!(
                 "{} {} registered successfully.\n",
                 "✔".green(),
                 format!("{} commands", commands.len()).bold()
             );
         }
 
-        commands
+        Ok(commands)
+    }
+
+    /// Test helper: panic with every problem `check_commands` finds
+    ///
+    /// Call it from an app's unit tests with the same list `main` registers.
+    pub fn assert_valid(commands: &[Termenu]) {
+        let problems = Self::check_commands(commands);
+        assert!(
+            problems.is_empty(),
+            "{} problem(s) with the registered commands:\n  - {}",
+            problems.len(),
+            problems.join("\n  - ")
+        );
     }
 
     /// Built-in help system: display all registered commands
-    fn show_help(
-        commands: &[Termenu],
-        specific: Option<&str>,
-        verbose: bool,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        let content = fs::read_to_string("Cargo.toml")?;
-        let cargo_toml: CargoToml = toml::from_str(&content)?;
-
-        Banner::render("Termenu");
+    fn show_help(commands: &[Termenu], verbose: bool) {
+        let info = AppInfo::current();
+        let width = layout::terminal_width();
+
+        Banner::render(&info.title);
+        if !info.description.trim().is_empty() {
+            println!("{}", layout::paragraph(&info.description, 0, width));
+        }
+        if let Some(authors) = info.authors_display() {
+            println!("{} {}", "Developer:".green(), authors.green().bold());
+        }
+        println!("{} {}", "Version:".green(), info.version.green().bold());
         println!(
-            "{} {}",
-            "Developer:".green(),
-            cargo_toml.package.authors[0].green().bold()
+            "{}\n  {} {}",
+            "Usage:".bold(),
+            info.name.green().bold(),
+            "[Global options] [Command] <options>".bold()
         );
         println!(
-            "{} {}",
-            "Version:".green(),
-            cargo_toml.package.version.green().bold()
+            "  Note: Check `{} help <command>` or `{} <command> --help` to view guide.",
+            info.name.bold(),
+            info.name.bold()
+        );
+        println!("{}", "Global Options:".yellow().bold());
+        let columns = Columns::new(2, GLOBAL_OPTIONS.iter().map(|(opt, _)| *opt), width);
+        for (opt, desc) in GLOBAL_OPTIONS {
+            println!("{}", columns.row(opt, opt.blue(), desc));
+        }
+
+        let mut groups = Self::grouped(commands);
+        if !verbose {
+            // Deprecated commands keep working but are only listed with --verbose
+            for (_, group) in &mut groups {
+                group.retain(|cmd| cmd.deprecation.is_none());
+            }
+            groups.retain(|(_, group)| !group.is_empty());
+        }
+        // One label column for every group so the descriptions line up across headings
+        let columns = Columns::new(
+            2,
+            groups
+                .iter()
+                .flat_map(|(_, group)| group.iter().map(|cmd| cmd.command.as_str())),
+            width,
         );
+        for (category, group) in groups {
+            let heading = category.unwrap_or("Available Commands");
+            println!("{}", format!("{}:", heading).yellow().bold());
+            for cmd in group {
+                Self::show_command(cmd, &columns, verbose, verbose, &info.name, width);
+            }
+        }
+    }
+
+    /// Help for one command: its usage, description, arguments, options and examples
+    fn show_command_help(cmd: &Termenu, verbose: bool) {
+        let program = AppInfo::current().name;
+        let width = layout::terminal_width();
         println!(
-            "{}\n  {} {}",
+            "{}\n  {}\n",
             "Usage:".bold(),
-            cargo_toml.package.name.green().bold(),
-            "[Command] <options>".bold()
+            docs::usage_line(&program, cmd)
         );
+        let columns = Columns::new(2, [cmd.command.as_str()], width);
+        Self::show_command(cmd, &columns, true, verbose, &program, width);
+    }
+
+    /// Visible commands grouped by category, uncategorized first, then in order of appearance
+    pub fn grouped(commands: &[Termenu]) -> Vec<(Option<&str>, Vec<&Termenu>)> {
+        Self::group(commands.iter().filter(|cmd| !cmd.hidden))
+    }
+
+    /// Like `grouped`, but with the commands generated docs list, see `hidden_from_docs`
+    pub fn grouped_for_docs(commands: &[Termenu]) -> Vec<(Option<&str>, Vec<&Termenu>)> {
+        Self::group(commands.iter().filter(|cmd| !cmd.hidden_from_docs))
+    }
+
+    fn group<'a>(
+        commands: impl Iterator<Item = &'a Termenu>,
+    ) -> Vec<(Option<&'a str>, Vec<&'a Termenu>)> {
+        let mut groups: Vec<(Option<&str>, Vec<&Termenu>)> = vec![(None, Vec::new())];
+        for cmd in commands {
+            let category = cmd.category.as_deref();
+            match groups.iter_mut().find(|(name, _)| *name == category) {
+                Some((_, group)) => group.push(cmd),
+                None => groups.push((category, vec![cmd])),
+            }
+        }
+        groups.retain(|(_, group)| !group.is_empty());
+        groups
+    }
+
+    /// One help entry; `detailed` adds arguments, options and examples
+    ///
+    /// Deprecated options are only listed when `show_deprecated` is set.
+    fn show_command(
+        cmd: &Termenu,
+        columns: &Columns,
+        detailed: bool,
+        show_deprecated: bool,
+        program: &str,
+        width: usize,
+    ) {
+        let mut summary = cmd.description.clone();
+        if !cmd.aliases.is_empty() {
+            summary.push_str(&format!(" (alias: {})", cmd.aliases.join(", ")));
+        }
+        if let Some(deprecation) = &cmd.deprecation {
+            summary.push_str(&format!(" ({})", deprecation.note()));
+        }
         println!(
-            "  Note: Check `{} help --command=<command_name>` to view guide.",
-            cargo_toml.package.name.bold()
+            "{}",
+            columns.row(&cmd.command, cmd.command.green(), &summary)
         );
-        println!("{}", "Available Commands:".yellow().bold());
+        if !detailed {
+            return;
+        }
+        if let Some(text) = &cmd.long_description {
+            println!("\n{}\n", layout::paragraph(text, 4, width));
+        }
 
-        for cmd in commands {
-            if let Some(spec) = specific {
-                if cmd.command != spec {
-                    continue;
-                }
+        let options: Vec<&CommandOption> = cmd
+            .options
+            .iter()
+            .filter(|opt| show_deprecated || opt.is_listed())
+            .collect();
+        let arg_labels: Vec<String> = cmd
+            .arguments
+            .iter()
+            .map(|arg| format!("<{}>", arg.name))
+            .collect();
+        let columns = Columns::new(
+            4,
+            arg_labels
+                .iter()
+                .map(String::as_str)
+                .chain(options.iter().map(|opt| opt.name.as_str())),
+            width,
+        );
+        for (arg, label) in cmd.arguments.iter().zip(&arg_labels) {
+            println!(
+                "{}",
+                columns.row(label, label.magenta(), &Self::option_help(arg))
+            );
+        }
+        for opt in options {
+            println!(
+                "{}",
+                columns.row(&opt.name, opt.name.blue(), &Self::option_help(opt))
+            );
+        }
+        Self::show_details(cmd, program, width);
+        println!();
+    }
+
+    /// Description with the default value, requirement and deprecation appended
+    fn option_help(option: &CommandOption) -> String {
+        let mut help = match &option.default {
+            Some(default) => format!("{} (default: {})", option.description, default),
+            None if option.required => format!("{} (required)", option.description),
+            None => option.description.clone(),
+        };
+        if let Some(deprecation) = &option.deprecation {
+            help.push_str(&format!(" ({})", deprecation.note()));
+        }
+        help
+    }
+
+    /// Examples and related commands of a single command
+    fn show_details(cmd: &Termenu, program: &str, width: usize) {
+        if !cmd.examples.is_empty() {
+            println!("\n{}", "Examples:".yellow().bold());
+            for example in &cmd.examples {
+                println!("  {} {} {}", "$".dimmed(), program.green(), example.command);
+                println!(
+                    "{}",
+                    layout::paragraph(&example.description, 6, width).dimmed()
+                );
             }
-            println!("  {:<10} • {}", cmd.command.green(), cmd.description);
-            if verbose || specific.is_some() {
-                for (opt, desc) in &cmd.options {
-                    println!("    {:<12} {}", opt.blue(), desc);
+        }
+        if !cmd.see_also.is_empty() {
+            println!(
+                "\n{} {}",
+                "See also:".yellow().bold(),
+                cmd.see_also.join(", ")
+            );
+        }
+    }
+
+    /// Process CLI input and execute matching command
+    pub async fn processor(commands: Vec<Termenu>) -> Result<(), TermenuError> {
+        Self::processor_with_state(commands, State::new()).await
+    }
+
+    /// Process CLI input with shared state available to every handler
+    pub async fn processor_with_state(
+        mut commands: Vec<Termenu>,
+        mut state: State,
+    ) -> Result<(), TermenuError> {
+        crash::install();
+        let app_commands = commands.len();
+
+        // --- Add built-in `jobs` for background job records ---
+        commands.push(jobs::register());
+
+        // --- Add built-in `version` ---
+        commands.push(version::register());
+
+        // --- Add built-in `completions` for shell scripts ---
+        commands.push(completions::register());
+
+        // --- Add built-in `man` for roff man pages ---
+        commands.push(manpage::register());
+
+        // --- Add built-in `docs` for the Markdown/HTML reference ---
+        commands.push(docs::register());
+
+        // --- Add built-in `explain` for error codes ---
+        commands.push(explain::register());
+
+        for cmd in &mut commands[app_commands..] {
+            cmd.category(BUILTIN_CATEGORY);
+        }
+
+        // --- Add built-in `help` dynamically ---
+        let help_bi = Self::help_command(&commands);
+        commands.push(help_bi);
+
+        state.insert(Registry::new(commands.clone()));
+        let state = Arc::new(state);
+
+        let all_args: Vec<String> = env::args().skip(1).collect();
+
+        // Hidden entry point used by the completion scripts for runtime values
+        if all_args.first().map(String::as_str) == Some("__complete") {
+            let words = match all_args.get(1).map(String::as_str) {
+                Some("--") => &all_args[2..],
+                _ => &all_args[1..],
+            };
+            for candidate in completions::complete(&commands, words) {
+                match candidate.description {
+                    Some(description) => println!("{}\t{}", candidate.value, description),
+                    None => println!("{}", candidate.value),
                 }
-                println!();
             }
+            return Ok(());
         }
+        let (globals, args) = GlobalOptions::parse(&all_args)?;
 
-        Ok(())
+        if globals.version {
+            BuildInfo::current().print();
+            return Ok(());
+        }
+
+        match Self::requested_help(&commands, globals.help, args) {
+            Some(HelpRequest::General) => {
+                Self::show_help(&commands, false);
+                return Ok(());
+            }
+            Some(HelpRequest::Command(cmd)) => {
+                Self::show_command_help(cmd, false);
+                return Ok(());
+            }
+            None => {}
+        }
+
+        let command_name = args[0].clone();
+        // Index of the command name in the full command line, program name included
+        let command_index = 1 + all_args.len() - args.len();
+
+        if let Some(termenu) = commands.iter().find(|t| t.is_named(&command_name)) {
+            let raw_options: &[String] = &args[1..];
+            termenu.warn_if_deprecated(&command_name);
+            let parsed_options =
+                termenu
+                    .parse_options(raw_options)
+                    .map_err(|e| match e.argument() {
+                        Some(index) => e.at_argument(command_index + 1 + index),
+                        None => e,
+                    })?;
+
+            if globals.background {
+                // Same invocation minus `--background`, run by a detached child
+                let global_count = all_args.len() - args.len();
+                let child_args: Vec<String> = all_args[..global_count]
+                    .iter()
+                    .filter(|a| a.as_str() != "--background")
+                    .chain(args.iter())
+                    .cloned()
+                    .collect();
+                let job = jobs::spawn(&child_args)?;
+                println!(
+                    "{} Started job {} (pid {})",
+                    "✔".green(),
+                    job.id.bold(),
+                    job.pid
+                );
+                println!(
+                    "  Check it with `{} jobs status {}`",
+                    AppInfo::current().name,
+                    &job.id[..8]
+                );
+                return Ok(());
+            }
+
+            let job_id = env::var(JOB_ID_ENV).ok();
+            if let Some(id) = &job_id {
+                // Job output goes to a log file
+                colored::control::set_override(false);
+                jobs::attach(id);
+            }
+
+            let result = Self::run_command(termenu, parsed_options, state, &globals).await;
+            if let Some(id) = job_id {
+                jobs::finish(&id, &result);
+            }
+            result
+        } else {
+            Err(Self::unknown_command_error(&commands, &command_name).at_argument(command_index))
+        }
     }
 
-    /// Process CLI input and execute matching command
-    pub async fn processor(mut commands: Vec<Termenu>) -> Result<(), TermenuError> {
-        // --- Clone commands for use inside the help closure ---
-        let help_commands = commands.clone();
+    /// Error for a name no command answers to, suggesting the closest listed one
+    fn unknown_command_error(commands: &[Termenu], name: &str) -> TermenuError {
+        let names = Self::listed_names(commands);
+        let hint = match report::closest(name, names.iter().map(String::as_str)) {
+            Some(name) => format!("Did you mean '{}'?", name),
+            None => format!(
+                "Run '{} help' to view available commands.",
+                AppInfo::current().name
+            ),
+        };
+        TermenuError::invalid_command_error(Some(json!({
+            "issue": format!("invalid command '{}'", name)
+        })))
+        .with_hint(&hint)
+    }
 
-        // --- Add built-in `help` dynamically ---
-        let mut help_bi: Termenu = Termenu::new_command(
+    /// Names and aliases of the commands shown in help and completions
+    fn listed_names(commands: &[Termenu]) -> Vec<String> {
+        commands
+            .iter()
+            .filter(|cmd| cmd.is_listed())
+            .flat_map(|cmd| std::iter::once(&cmd.command).chain(&cmd.aliases))
+            .cloned()
+            .collect()
+    }
+
+    /// Help asked for with `--help`/`-h`, or by giving no command at all
+    ///
+    /// `help` is the global `--help` flag and `args` the command line after the global options.
+    fn requested_help<'a>(
+        commands: &'a [Termenu],
+        help: bool,
+        args: &[String],
+    ) -> Option<HelpRequest<'a>> {
+        let Some(name) = args.first() else {
+            return Some(HelpRequest::General);
+        };
+        let command = commands.iter().find(|cmd| cmd.is_named(name));
+        if help {
+            // `--help <command>` shows that command's help
+            return Some(command.map_or(HelpRequest::General, HelpRequest::Command));
+        }
+        let asked = args[1..].iter().any(|arg| arg == "--help" || arg == "-h");
+        command.filter(|_| asked).map(HelpRequest::Command)
+    }
+
+    /// The built-in `help`, for `commands` plus itself
+    ///
+    /// `help <command>` accepts every name a command answers to, but only suggests listed ones.
+    fn help_command(commands: &[Termenu]) -> Termenu {
+        let mut help_bi: Termenu = Termenu::new_command_with_context(
             "help",
             "Show usage guide and command descriptions.",
-            move |options| {
-                let verbose = options.contains_key("--verbose");
-                let specific = options.get("--command").and_then(|v| v.clone());
-                Termenu::show_help(&help_commands, specific.as_deref(), verbose);
+            |ctx: &Context| {
+                let registry = ctx.state::<Registry>()?;
+                if ctx.option("--format") == Some("json") {
+                    return Schema::new(registry.commands(), &AppInfo::current()).print_json();
+                }
+                let verbose = ctx.has_option("--verbose");
+                match ctx.option("--command").or(ctx.option("command")) {
+                    Some(name) => {
+                        let cmd = registry.find(name).ok_or_else(|| {
+                            Termenu::unknown_command_error(registry.commands(), name)
+                        })?;
+                        Termenu::show_command_help(cmd, verbose);
+                    }
+                    None => Termenu::show_help(registry.commands(), verbose),
+                }
                 Ok(())
             },
         );
 
         // ✅ add options properly (mut required)
-        help_bi.add_option("--verbose", "Display detailed usage information.");
-        help_bi.add_option(
-            "--command",
-            "Show help for a specific command (e.g. --command=test).",
+        help_bi
+            .add_option("--verbose", "Display detailed usage information.")
+            .flag();
+        help_bi
+            .add_option(
+                "--format",
+                "Output format; json prints the versioned command schema.",
+            )
+            .choices(&["text", "json"])
+            .default_value("text");
+
+        let mut names = Self::listed_names(commands);
+        names.push("help".to_string());
+        let complete = move |partial: &str| -> Vec<Candidate> {
+            names
+                .iter()
+                .filter(|name| name.starts_with(partial))
+                .map(|name| Candidate::new(name))
+                .collect()
+        };
+        help_bi
+            .add_option(
+                "--command",
+                "Show help for a specific command (e.g. --command=test).",
+            )
+            .value()
+            .complete_with(complete.clone());
+        help_bi
+            .add_argument("command", "Command to show help for; same as --command.")
+            .complete_with(complete);
+        help_bi.add_example(
+            "help --format=json",
+            "Describe every command as JSON, e.g. to generate a client.",
         );
 
-        commands.push(help_bi);
+        help_bi
+            .category(BUILTIN_CATEGORY)
+            .error_codes(&["E712", "E112", "E013", "E011"]);
+        help_bi
+    }
 
-        let args: Vec<String> = env::args().collect();
+    /// Run a command once, or repeatedly in watch/schedule mode
+    async fn run_command(
+        termenu: &Termenu,
+        parsed_options: HashMap<String, Option<String>>,
+        state: Arc<State>,
+        globals: &GlobalOptions,
+    ) -> Result<(), TermenuError> {
+        if let Some(watch_config) = &globals.watch {
+            watch(watch_config, || {
+                termenu.execute_with_context(Context::new(parsed_options.clone(), state.clone()))
+            })
+            .await
+        } else if let Some(schedule_config) = &globals.schedule {
+            run_scheduled(schedule_config, || {
+                termenu.execute_with_context(Context::new(parsed_options.clone(), state.clone()))
+            })
+            .await
+        } else {
+            termenu
+                .execute_with_context(Context::new(parsed_options, state))
+                .await
+        }
+    }
+}
 
-        if args.len() < 2 {
-            Self::show_help(&commands, None, false);
-            return Ok(());
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grouped_skips_hidden_and_keeps_category_order() {
+        let command = |name: &str| Termenu::new_command(name, "A command.", |_| Ok(()));
+        let mut deploy = command("deploy");
+        deploy.category("Ops");
+        let mut secret = command("secret");
+        secret.category("Ops").hidden();
+        let mut status = command("status");
+        status.category("Ops");
+
+        let mut shown = command("shown");
+        shown.category("Ops").hidden().hidden_if(false);
+
+        let commands = vec![deploy, command("hello"), secret, status, shown];
+        let groups: Vec<(Option<&str>, Vec<&str>)> = Termenu::grouped(&commands)
+            .into_iter()
+            .map(|(category, group)| (category, group.iter().map(|c| c.command.as_str()).collect()))
+            .collect();
+
+        assert_eq!(
+            groups,
+            vec![
+                (None, vec!["hello"]),
+                (Some("Ops"), vec!["deploy", "status", "shown"])
+            ]
+        );
+        let documented: Vec<&str> = Termenu::grouped_for_docs(&commands)
+            .into_iter()
+            .flat_map(|(_, group)| group.into_iter().map(|c| c.command.as_str()))
+            .collect();
+        assert_eq!(documented, ["hello", "deploy", "status", "shown"]);
+    }
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_help_flags_after_a_command_ask_for_its_help() {
+        let commands = vec![Termenu::new_command("deploy", "Deploy it.", |_| Ok(()))];
+        for flag in ["--help", "-h"] {
+            let request = Termenu::requested_help(&commands, false, &args(&["deploy", flag]));
+            assert!(matches!(request, Some(HelpRequest::Command(cmd)) if cmd.command == "deploy"));
         }
+        let request = Termenu::requested_help(&commands, true, &args(&["deploy"]));
+        assert!(matches!(request, Some(HelpRequest::Command(cmd)) if cmd.command == "deploy"));
+        let request = Termenu::requested_help(&commands, false, &[]);
+        assert!(matches!(request, Some(HelpRequest::General)));
+        assert!(Termenu::requested_help(&commands, false, &args(&["deploy", "--x"])).is_none());
+    }
 
-        let command_name = args[1].clone();
+    #[test]
+    fn test_help_command_accepts_hidden_names_but_suggests_listed_ones() {
+        let mut secret = Termenu::new_command("secret", "Not listed.", |_| Ok(()));
+        secret.hidden();
+        let commands = vec![
+            Termenu::new_command("deploy", "Deploy it.", |_| Ok(())),
+            secret,
+        ];
+        let help = Termenu::help_command(&commands);
 
-        if let Some(termenu) = commands.iter().find(|t| t.command == command_name) {
-            let raw_options: &[String] = &args[2..];
-            let parsed_options = termenu.parse_options(raw_options)?;
+        let parsed = help.parse_options(&args(&["secret"])).unwrap();
+        assert_eq!(parsed.get("command"), Some(&Some("secret".to_string())));
+        assert!(Registry::new(commands.clone()).find("secret").is_some());
 
-            if let Err(err) = termenu.execute(parsed_options).await {
-                return Err(err); // ✅ keep original error (no double wrap)
-            }
-        } else {
-            return Err(TermenuError::invalid_command_error(Some(json!({
-                "issue": format!(
-                    "invalid command '{}'. Run with 'help' to view available commands.",
-                    command_name
-                )
-            }))));
+        for suggestions in [
+            help.arguments[0].complete(""),
+            help.find_option("--command").unwrap().complete(""),
+        ] {
+            let values: Vec<String> = suggestions.into_iter().map(|c| c.value).collect();
+            assert_eq!(values, ["deploy", "help"]);
         }
+    }
+
+    #[test]
+    fn test_renamed_option_is_stored_under_its_new_name() {
+        let mut command = Termenu::new_command("build", "Build it.", |_| Ok(()));
+        command.renamed_from("compile", Some("2.0.0"));
+        command
+            .add_option("--output", "Output directory.")
+            .value()
+            .renamed_from("--out", Some("2.0.0"));
+
+        assert!(command.is_named("compile"));
+        let parsed = command.parse_options(&["--out=dist".to_string()]).unwrap();
+        assert_eq!(parsed.get("--output"), Some(&Some("dist".to_string())));
+        assert!(!parsed.contains_key("--out"));
+    }
+
+    #[test]
+    fn test_check_commands_reports_every_problem() {
+        let mut build = Termenu::new_command("build", "Build it.", |_| Ok(()));
+        build.add_option("release", "Optimize.").flag();
+        build.add_option("--out", "Output.").value();
+        build.add_option("--out", "Output again.").value();
+        let mut help = Termenu::new_command("help", "My own help.", |_| Ok(()));
+        help.handler = None;
+        let mut duplicate = Termenu::new_command("build", "", |_| Ok(()));
+        duplicate.add_argument("--target", "Target.");
+        let unnamed = || Termenu::new_command("", "No name.", |_| Ok(()));
+        let complete = Termenu::new_command("__complete", "Shadowed.", |_| Ok(()));
+
+        let problems =
+            Termenu::check_commands(&[build, help, duplicate, unnamed(), unnamed(), complete]);
+        assert_eq!(
+            problems,
+            vec![
+                "Option 'release' of 'build' must start with '--'",
+                "Duplicate option '--out' in 'build'",
+                "Command 'help' has no handler",
+                "Command name 'help' collides with the built-in 'help'",
+                "Command description missing for 'build'",
+                "Duplicate command detected: 'build'",
+                "Argument '--target' of 'build' must be a plain name like 'file'",
+                "Command name cannot be empty",
+                "Command name cannot be empty",
+                "Command name '__complete' collides with the built-in '__complete'",
+            ]
+        );
+        assert!(Termenu::validate_commands(Vec::new()).is_err());
+    }
+
+    #[test]
+    fn test_parse_errors_point_at_the_argument_with_a_hint() {
+        let mut command = Termenu::new_command("build", "Build it.", |_| Ok(()));
+        command.add_option("--release", "Optimize.").flag();
 
-        Ok(())
+        let args = ["--release".to_string(), "--relase".to_string()];
+        let error = command.parse_options(&args).unwrap_err();
+        assert_eq!(error.argument(), Some(1));
+        assert_eq!(error.hint(), Some("Did you mean '--release'?"));
     }
 }
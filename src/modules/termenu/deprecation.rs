@@ -0,0 +1,96 @@
+use colored::Colorize;
+use serde::Serialize;
+
+use super::report::on_stderr;
+
+/// Marks a command or option name as on its way out
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Deprecation {
+    /// Name to use instead, if there is one
+    pub replacement: Option<String>,
+    /// Version in which the name goes away
+    pub removal: Option<String>,
+}
+
+impl Deprecation {
+    pub fn new(replacement: Option<&str>, removal: Option<&str>) -> Self {
+        Self {
+            replacement: replacement.map(str::to_string),
+            removal: removal.map(str::to_string),
+        }
+    }
+
+    /// Short note for help and docs, e.g. `deprecated, removed in 2.0; use --output`
+    pub fn note(&self) -> String {
+        let mut note = String::from("deprecated");
+        if let Some(removal) = &self.removal {
+            note.push_str(&format!(", removed in {}", removal));
+        }
+        if let Some(replacement) = &self.replacement {
+            note.push_str(&format!("; use {}", replacement));
+        }
+        note
+    }
+
+    /// Warning shown when `name` is used
+    pub fn message(&self, kind: &str, name: &str) -> String {
+        let removal = match &self.removal {
+            Some(version) => format!("will be removed in {}", version),
+            None => "will be removed in a future version".to_string(),
+        };
+        match &self.replacement {
+            Some(replacement) => format!(
+                "{} '{}' is deprecated and {}. Use '{}' instead.",
+                kind, name, removal, replacement
+            ),
+            None => format!("{} '{}' is deprecated and {}.", kind, name, removal),
+        }
+    }
+
+    /// Print the warning to stderr so piped output is unaffected
+    pub fn warn(&self, kind: &str, name: &str) {
+        eprintln!(
+            "{} {}",
+            on_stderr("Warning:".yellow().bold()),
+            self.message(kind, name)
+        );
+    }
+}
+
+/// `description` followed by deprecation sentences for reference docs, e.g.
+/// `Write here. Deprecated (...). Old name: --out (...).`
+pub fn annotate(
+    description: &str,
+    deprecation: Option<&Deprecation>,
+    renamed_from: &[(String, Deprecation)],
+) -> String {
+    let mut text = description.to_string();
+    if let Some(deprecation) = deprecation {
+        text.push_str(&format!(" Deprecated ({}).", deprecation.note()));
+    }
+    for (old, deprecation) in renamed_from {
+        text.push_str(&format!(" Old name: {} ({}).", old, deprecation.note()));
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_message_names_replacement_and_removal() {
+        let renamed = Deprecation::new(Some("--output"), Some("2.0.0"));
+        assert_eq!(
+            renamed.message("Option", "--out"),
+            "Option '--out' is deprecated and will be removed in 2.0.0. Use '--output' instead."
+        );
+        assert_eq!(renamed.note(), "deprecated, removed in 2.0.0; use --output");
+
+        let dropped = Deprecation::new(None, None);
+        assert_eq!(
+            dropped.message("Command", "legacy"),
+            "Command 'legacy' is deprecated and will be removed in a future version."
+        );
+    }
+}
@@ -0,0 +1,517 @@
+use colored::Colorize;
+use serde_json::json;
+use std::fs;
+use std::path::Path;
+
+use super::app_info::AppInfo;
+use super::context::Context;
+use super::core::{Registry, Termenu};
+use super::deprecation;
+use super::exceptions::{ErrorKind, TermenuError};
+use super::globals::{GLOBAL_FLAGS, GLOBAL_OPTIONS};
+use super::option::CommandOption;
+
+pub const FORMATS: &[&str] = &["markdown", "html"];
+
+/// Markers delimiting the generated reference inside a hand-written file (e.g. the README)
+pub const DOCS_START: &str = "<!-- termenu-docs:start -->";
+pub const DOCS_END: &str = "<!-- termenu-docs:end -->";
+
+/// Output format of the reference
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DocsFormat {
+    Markdown,
+    Html,
+}
+
+impl DocsFormat {
+    pub fn parse(value: &str) -> Result<Self, TermenuError> {
+        match value {
+            "markdown" => Ok(Self::Markdown),
+            "html" => Ok(Self::Html),
+            other => Err(TermenuError::input_unknown_error(Some(json!({
+                "issue": format!("Unknown docs format '{}'. Use {}.", other, FORMATS.join(" or "))
+            })))),
+        }
+    }
+}
+
+/// Usage label for an option, e.g. `--format=<text|json>`
+fn option_label(option: &CommandOption) -> String {
+    option.label(str::to_string, |value| format!("<{}>", value))
+}
+
+fn global_label(name: &str) -> String {
+    if GLOBAL_FLAGS.contains(&name) {
+        name.to_string()
+    } else {
+        format!("{}=<value>", name)
+    }
+}
+
+/// One-line usage of a command, e.g. `termenu version [--format=<text|json>]`
+pub fn usage_line(program: &str, command: &Termenu) -> String {
+    let mut parts = vec![program.to_string(), command.command.clone()];
+    parts.extend(
+        command
+            .options
+            .iter()
+            .map(|opt| format!("[{}]", option_label(opt))),
+    );
+    parts.extend(
+        command
+            .arguments
+            .iter()
+            .map(|arg| format!("[<{}>]", arg.name)),
+    );
+    parts.join(" ")
+}
+
+/// Examples as a shell snippet, each preceded by its explanation as a comment
+fn examples_block(program: &str, command: &Termenu) -> String {
+    command
+        .examples
+        .iter()
+        .map(|example| {
+            format!(
+                "# {}\n{} {}\n",
+                example.description, program, example.command
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Command description with its deprecation notes
+fn command_details(command: &Termenu) -> String {
+    deprecation::annotate(
+        &command.description,
+        command.deprecation.as_ref(),
+        &command.renamed_from,
+    )
+}
+
+/// Documented commands in help order, i.e. grouped by category
+fn listed(commands: &[Termenu]) -> Vec<&Termenu> {
+    Termenu::grouped_for_docs(commands)
+        .into_iter()
+        .flat_map(|(_, group)| group)
+        .collect()
+}
+
+/// Escape `|` so text can sit inside a Markdown table cell
+fn md_cell(text: &str) -> String {
+    text.replace('|', "\\|")
+}
+
+/// Table cell text outside of code spans, where `<name>` would be read as HTML
+fn md_text(text: &str) -> String {
+    md_cell(text).replace('<', "&lt;")
+}
+
+fn md_option_table(options: &[CommandOption], argument: bool) -> String {
+    let mut out = String::from("| Option | Description | Default |\n| --- | --- | --- |\n");
+    if argument {
+        out = out.replacen("Option", "Argument", 1);
+    }
+    for opt in options {
+        let label = if argument {
+            format!("<{}>", opt.name)
+        } else {
+            option_label(opt)
+        };
+        let default = opt
+            .default
+            .as_ref()
+            .map(|d| format!("`{}`", md_cell(d)))
+            .unwrap_or_default();
+        out.push_str(&format!(
+            "| `{}` | {} | {} |\n",
+            md_cell(&label),
+            md_text(&opt.details()),
+            default
+        ));
+    }
+    out
+}
+
+/// Command reference as Markdown, starting at `###` headings so it can sit under a README section
+pub fn render_markdown(commands: &[Termenu], info: &AppInfo) -> String {
+    let commands = listed(commands);
+    let mut out = String::new();
+
+    out.push_str(&format!(
+        "### Synopsis\n\n```text\n{} [global options] <command> [options]\n```\n\n",
+        info.name
+    ));
+
+    out.push_str("### Global options\n\nGlobal options go before the command name.\n\n");
+    out.push_str("| Option | Description |\n| --- | --- |\n");
+    for (name, description) in GLOBAL_OPTIONS {
+        out.push_str(&format!(
+            "| `{}` | {} |\n",
+            md_cell(&global_label(name)),
+            md_text(description)
+        ));
+    }
+
+    out.push_str("\n### Commands\n\n| Command | Category | Description |\n| --- | --- | --- |\n");
+    for cmd in &commands {
+        out.push_str(&format!(
+            "| [`{}`](#{}) | {} | {} |\n",
+            cmd.command,
+            cmd.command,
+            md_text(cmd.category.as_deref().unwrap_or_default()),
+            md_text(&command_details(cmd))
+        ));
+    }
+
+    for cmd in &commands {
+        out.push_str(&format!(
+            "\n#### `{}`\n\n{}\n\n",
+            cmd.command,
+            md_text(&command_details(cmd))
+        ));
+        if let Some(text) = &cmd.long_description {
+            out.push_str(&format!("{}\n\n", md_text(text)));
+        }
+        out.push_str(&format!("```text\n{}\n```\n", usage_line(&info.name, cmd)));
+        if !cmd.arguments.is_empty() {
+            out.push('\n');
+            out.push_str(&md_option_table(&cmd.arguments, true));
+        }
+        if !cmd.options.is_empty() {
+            out.push('\n');
+            out.push_str(&md_option_table(&cmd.options, false));
+        }
+        if !cmd.examples.is_empty() {
+            out.push_str(&format!(
+                "\nExamples:\n\n```sh\n{}```\n",
+                examples_block(&info.name, cmd)
+            ));
+        }
+        if !cmd.see_also.is_empty() {
+            let links: Vec<String> = cmd
+                .see_also
+                .iter()
+                .map(|name| format!("[`{0}`](#{0})", name))
+                .collect();
+            out.push_str(&format!("\nSee also: {}\n", links.join(", ")));
+        }
+    }
+
+    out.push_str(&format!(
+        "\n### Errors\n\nErrors are reported as `[code] Name: issue`. Run `{} explain <code>` for common causes and remedies.\n\n",
+        info.name
+    ));
+    out.push_str("| Code | Name | Category | Meaning |\n| --- | --- | --- | --- |\n");
+    for kind in ErrorKind::all() {
+        out.push_str(&format!(
+            "| `{}` | {} | {} | {} |\n",
+            kind.code(),
+            kind.name(),
+            kind.category().label(),
+            md_text(kind.message())
+        ));
+    }
+
+    out
+}
+
+fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn html_option_table(options: &[CommandOption], argument: bool) -> String {
+    let mut out = format!(
+        "<table>\n<tr><th>{}</th><th>Description</th><th>Default</th></tr>\n",
+        if argument { "Argument" } else { "Option" }
+    );
+    for opt in options {
+        let label = if argument {
+            format!("<{}>", opt.name)
+        } else {
+            option_label(opt)
+        };
+        let default = opt
+            .default
+            .as_ref()
+            .map(|d| format!("<code>{}</code>", html_escape(d)))
+            .unwrap_or_default();
+        out.push_str(&format!(
+            "<tr><td><code>{}</code></td><td>{}</td><td>{}</td></tr>\n",
+            html_escape(&label),
+            html_escape(&opt.details()),
+            default
+        ));
+    }
+    out.push_str("</table>\n");
+    out
+}
+
+/// Command reference as an HTML fragment, without `<html>` or styles
+fn render_html_body(commands: &[Termenu], info: &AppInfo) -> String {
+    let commands = listed(commands);
+    let mut out = String::new();
+
+    out.push_str(&format!(
+        "<h2>Synopsis</h2>\n<pre>{} [global options] &lt;command&gt; [options]</pre>\n",
+        html_escape(&info.name)
+    ));
+
+    out.push_str("<h2>Global options</h2>\n<p>Global options go before the command name.</p>\n");
+    out.push_str("<table>\n<tr><th>Option</th><th>Description</th></tr>\n");
+    for (name, description) in GLOBAL_OPTIONS {
+        out.push_str(&format!(
+            "<tr><td><code>{}</code></td><td>{}</td></tr>\n",
+            html_escape(&global_label(name)),
+            html_escape(description)
+        ));
+    }
+    out.push_str("</table>\n");
+
+    out.push_str("<h2>Commands</h2>\n<table>\n<tr><th>Command</th><th>Category</th><th>Description</th></tr>\n");
+    for cmd in &commands {
+        out.push_str(&format!(
+            "<tr><td><a href=\"#{0}\"><code>{0}</code></a></td><td>{1}</td><td>{2}</td></tr>\n",
+            html_escape(&cmd.command),
+            html_escape(cmd.category.as_deref().unwrap_or_default()),
+            html_escape(&command_details(cmd))
+        ));
+    }
+    out.push_str("</table>\n");
+
+    for cmd in &commands {
+        out.push_str(&format!(
+            "<h3 id=\"{0}\"><code>{0}</code></h3>\n<p>{1}</p>\n<pre>{2}</pre>\n",
+            html_escape(&cmd.command),
+            html_escape(&command_details(cmd)),
+            html_escape(&usage_line(&info.name, cmd))
+        ));
+        if let Some(text) = &cmd.long_description {
+            for paragraph in text.split("\n\n") {
+                out.push_str(&format!("<p>{}</p>\n", html_escape(paragraph.trim())));
+            }
+        }
+        if !cmd.arguments.is_empty() {
+            out.push_str(&html_option_table(&cmd.arguments, true));
+        }
+        if !cmd.options.is_empty() {
+            out.push_str(&html_option_table(&cmd.options, false));
+        }
+        if !cmd.examples.is_empty() {
+            out.push_str(&format!(
+                "<p>Examples:</p>\n<pre>{}</pre>\n",
+                html_escape(&examples_block(&info.name, cmd))
+            ));
+        }
+        if !cmd.see_also.is_empty() {
+            let links: Vec<String> = cmd
+                .see_also
+                .iter()
+                .map(|name| format!("<a href=\"#{0}\"><code>{0}</code></a>", html_escape(name)))
+                .collect();
+            out.push_str(&format!("<p>See also: {}</p>\n", links.join(", ")));
+        }
+    }
+
+    out.push_str(
+        &format!(
+            "<h2>Errors</h2>\n<p>Errors are reported as <code>[code] Name: issue</code>. Run <code>{} explain &lt;code&gt;</code> for common causes and remedies.</p>\n",
+            html_escape(&info.name)
+        ),
+    );
+    out.push_str("<table>\n<tr><th>Code</th><th>Name</th><th>Category</th><th>Meaning</th></tr>\n");
+    for kind in ErrorKind::all() {
+        out.push_str(&format!(
+            "<tr><td><code>{}</code></td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+            kind.code(),
+            kind.name(),
+            kind.category().label(),
+            html_escape(kind.message())
+        ));
+    }
+    out.push_str("</table>\n");
+
+    out
+}
+
+const HTML_STYLE: &str = "body{font-family:system-ui,sans-serif;max-width:56rem;margin:2rem auto;padding:0 1rem;line-height:1.5;color:#1f2328}\
+code,pre{font-family:ui-monospace,monospace;background:#f6f8fa;border-radius:4px}\
+code{padding:.1em .3em}pre{padding:.75rem 1rem;overflow-x:auto}\
+table{border-collapse:collapse;margin:1rem 0;width:100%}\
+th,td{border:1px solid #d0d7de;padding:.4rem .6rem;text-align:left;vertical-align:top}\
+th{background:#f6f8fa}h3{margin-top:2rem}";
+
+/// Self-contained HTML page with inline styles and no external assets
+pub fn render_html(commands: &[Termenu], info: &AppInfo) -> String {
+    let title = format!("{} command reference", info.name);
+    let mut out = format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{0}</title>\n<style>{1}</style>\n</head>\n<body>\n<h1>{0}</h1>\n",
+        html_escape(&title),
+        HTML_STYLE
+    );
+    if !info.description.trim().is_empty() {
+        out.push_str(&format!("<p>{}</p>\n", html_escape(&info.description)));
+    }
+    out.push_str(&render_html_body(commands, info));
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+/// Full document in the requested format
+pub fn render(commands: &[Termenu], info: &AppInfo, format: DocsFormat) -> String {
+    match format {
+        DocsFormat::Markdown => {
+            let mut out = format!("# {} command reference\n\n", info.name);
+            if !info.description.trim().is_empty() {
+                out.push_str(&format!("{}\n\n", info.description));
+            }
+            out.push_str(&render_markdown(commands, info));
+            out
+        }
+        DocsFormat::Html => render_html(commands, info),
+    }
+}
+
+/// Replace the text between `DOCS_START` and `DOCS_END`, if both markers are present
+pub fn splice(existing: &str, generated: &str) -> Option<String> {
+    let start = existing.find(DOCS_START)? + DOCS_START.len();
+    let end = start + existing[start..].find(DOCS_END)?;
+    Some(format!(
+        "{}\n{}{}",
+        &existing[..start],
+        generated,
+        &existing[end..]
+    ))
+}
+
+/// What `path` should contain: the spliced file when it has markers, otherwise a full document
+pub fn expected_contents(
+    commands: &[Termenu],
+    info: &AppInfo,
+    format: DocsFormat,
+    existing: Option<&str>,
+) -> String {
+    let fragment = match format {
+        DocsFormat::Markdown => render_markdown(commands, info),
+        DocsFormat::Html => render_html_body(commands, info),
+    };
+    existing
+        .and_then(|text| splice(text, &fragment))
+        .unwrap_or_else(|| render(commands, info, format))
+}
+
+/// Built-in `docs` command
+pub fn register() -> Termenu {
+    let mut command = Termenu::new_command_with_context(
+        "docs",
+        "Generate the command reference as Markdown or HTML.",
+        |ctx: &Context| {
+            let registry = ctx.state::<Registry>()?;
+            let info = AppInfo::current();
+            let format = DocsFormat::parse(ctx.option("--format").unwrap_or("markdown"))?;
+
+            let Some(output) = ctx.option("--output") else {
+                if ctx.has_option("--check") {
+                    return Err(TermenuError::input_missing_error(Some(json!({
+                        "issue": "--check needs the file to compare against. Use `--output=<file>`."
+                    }))));
+                }
+                print!("{}", render(registry.commands(), &info, format));
+                return Ok(());
+            };
+
+            let path = Path::new(output);
+            let existing = fs::read_to_string(path).ok();
+            let expected =
+                expected_contents(registry.commands(), &info, format, existing.as_deref());
+
+            if ctx.has_option("--check") {
+                if existing.as_deref() == Some(expected.as_str()) {
+                    println!("{} {} is up to date", "✔".green(), output);
+                    return Ok(());
+                }
+                return Err(TermenuError::command_unknown_error(Some(json!({
+                    "issue": format!(
+                        "{} is out of date. Run `{} docs --output={}` to update it.",
+                        output, info.name, output
+                    )
+                }))));
+            }
+
+            fs::write(path, &expected).map_err(|e| {
+                TermenuError::framework_resource_error(Some(json!({
+                    "issue": format!("Failed to write {}: {}", output, e)
+                })))
+            })?;
+            println!("{} Wrote command reference to {}", "✔".green(), output);
+            Ok(())
+        },
+    );
+
+    command
+        .add_option("--format", "Output format.")
+        .choices(FORMATS)
+        .default_value("markdown");
+    command
+        .add_option(
+            "--output",
+            "Write to this file; only the part between the docs markers is replaced if present.",
+        )
+        .value();
+    command
+        .add_option(
+            "--check",
+            "Fail if the --output file differs from the generated reference.",
+        )
+        .flag();
+
+    command
+        .long_description(
+            "Without --output the full reference is printed. When the --output file contains the <!-- termenu-docs:start --> and <!-- termenu-docs:end --> markers, only the text between them is replaced, so the reference can live inside a hand-written README.",
+        )
+        .add_example("docs --output=README.md", "Update the reference section of the README.")
+        .add_example("docs --output=README.md --check", "Fail in CI when the README is out of date.")
+        .add_example("docs --format=html > reference.html", "Write a self-contained HTML page.")
+        .see_also(&["man", "help"])
+        .error_codes(&["E112", "E711", "E013"]);
+
+    command
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_splice_replaces_only_the_marked_section() {
+        let readme = format!("# App\n\n{}\nold\n{}\n\nFooter\n", DOCS_START, DOCS_END);
+        let spliced = splice(&readme, "new\n").unwrap();
+        assert_eq!(
+            spliced,
+            format!("# App\n\n{}\nnew\n{}\n\nFooter\n", DOCS_START, DOCS_END)
+        );
+        // Splicing is stable, so `--check` passes right after writing
+        assert_eq!(splice(&spliced, "new\n").unwrap(), spliced);
+        assert!(splice("no markers here", "new\n").is_none());
+    }
+
+    #[test]
+    fn test_markdown_lists_examples_and_related_commands() {
+        let mut command = Termenu::new_command("greet", "Say hello.", |_| Ok(()));
+        command
+            .long_description("Greets | politely.")
+            .add_example("greet --name=Ann", "Greet Ann.")
+            .see_also(&["help"]);
+        let info = AppInfo::new("app", "1.0.0", "", "");
+
+        let markdown = render_markdown(&[command], &info);
+        assert!(markdown.contains("Greets \\| politely."));
+        assert!(markdown.contains("```sh\n# Greet Ann.\napp greet --name=Ann\n```"));
+        assert!(markdown.contains("See also: [`help`](#help)"));
+    }
+}
@@ -1,119 +1,486 @@
-use colored::*;
-use serde_json::Value;
+use serde_json::{Value, json};
 use std::error::Error;
-use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::sync::RwLock;
+use std::{fmt, io};
+
+use super::explain::Explanation;
+
+/// Codes apps may use for their own kinds, see `ErrorKind::register`
+pub const CUSTOM_CODES: std::ops::RangeInclusive<u16> = 800..=999;
+
+static CUSTOM_KINDS: RwLock<Vec<&'static CustomKind>> = RwLock::new(Vec::new());
+
+/// Group an error kind belongs to, matching the hundreds digit of its code
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorCategory {
+    Framework,
+    Input,
+    Connection,
+    Command,
+    /// Kinds registered by the app, E800–E999
+    App,
+}
+
+impl ErrorCategory {
+    pub fn label(self) -> &'static str {
+        match self {
+            ErrorCategory::Framework => "Framework",
+            ErrorCategory::Input => "Input",
+            ErrorCategory::Connection => "Connection",
+            ErrorCategory::Command => "Command",
+            ErrorCategory::App => "App",
+        }
+    }
+}
+
+/// Every error Termenu can report, each with its own code
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorKind {
+    Framework,
+    FrameworkForbidden,
+    FrameworkResource,
+    Input,
+    InputMissing,
+    Connection,
+    ConnectionTimeout,
+    Command,
+    InvalidCommand,
+    CommandTimeout,
+    /// Registered by the app with `ErrorKind::register`
+    Custom(&'static CustomKind),
+}
+
+impl ErrorKind {
+    pub const ALL: &'static [ErrorKind] = &[
+        ErrorKind::Framework,
+        ErrorKind::FrameworkForbidden,
+        ErrorKind::FrameworkResource,
+        ErrorKind::Input,
+        ErrorKind::InputMissing,
+        ErrorKind::Connection,
+        ErrorKind::ConnectionTimeout,
+        ErrorKind::Command,
+        ErrorKind::InvalidCommand,
+        ErrorKind::CommandTimeout,
+    ];
+
+    pub fn code(self) -> &'static str {
+        match self {
+            ErrorKind::Framework => "E011",
+            ErrorKind::FrameworkForbidden => "E012",
+            ErrorKind::FrameworkResource => "E013",
+            ErrorKind::Input => "E111",
+            ErrorKind::InputMissing => "E112",
+            ErrorKind::Connection => "E611",
+            ErrorKind::ConnectionTimeout => "E612",
+            ErrorKind::Command => "E711",
+            ErrorKind::InvalidCommand => "E712",
+            ErrorKind::CommandTimeout => "E713",
+            ErrorKind::Custom(custom) => &custom.code,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ErrorKind::Framework => "FrameworkError",
+            ErrorKind::FrameworkForbidden => "FrameworkForbiddenError",
+            ErrorKind::FrameworkResource => "FrameworkResourceError",
+            ErrorKind::Input => "InputError",
+            ErrorKind::InputMissing => "InputMissingError",
+            ErrorKind::Connection => "ConnectionError",
+            ErrorKind::ConnectionTimeout => "ConnectionTimeoutError",
+            ErrorKind::Command => "CommandError",
+            ErrorKind::InvalidCommand => "InvalidCommandError",
+            ErrorKind::CommandTimeout => "CommandTimeoutError",
+            ErrorKind::Custom(custom) => &custom.name,
+        }
+    }
+
+    pub fn category(self) -> ErrorCategory {
+        match self {
+            ErrorKind::Framework | ErrorKind::FrameworkForbidden | ErrorKind::FrameworkResource => {
+                ErrorCategory::Framework
+            }
+            ErrorKind::Input | ErrorKind::InputMissing => ErrorCategory::Input,
+            ErrorKind::Connection | ErrorKind::ConnectionTimeout => ErrorCategory::Connection,
+            ErrorKind::Command | ErrorKind::InvalidCommand | ErrorKind::CommandTimeout => {
+                ErrorCategory::Command
+            }
+            ErrorKind::Custom(_) => ErrorCategory::App,
+        }
+    }
+
+    /// Shown when the error carries no `issue` of its own
+    pub fn message(self) -> &'static str {
+        match self {
+            ErrorKind::Framework => "An unexpected internal error occurred.",
+            ErrorKind::FrameworkForbidden => "This action is not allowed here.",
+            ErrorKind::FrameworkResource => "A required resource is missing or unavailable.",
+            ErrorKind::Input => "The input is not valid.",
+            ErrorKind::InputMissing => "A required input is missing.",
+            ErrorKind::Connection => "A network request failed.",
+            ErrorKind::ConnectionTimeout => "A network request timed out.",
+            ErrorKind::Command => "The command failed.",
+            ErrorKind::InvalidCommand => "The command or its arguments are not valid.",
+            ErrorKind::CommandTimeout => "The command timed out.",
+            ErrorKind::Custom(custom) => &custom.message,
+        }
+    }
+
+    /// Process exit code when a command fails with this kind; 1 unless the app chose one
+    pub fn exit_code(self) -> i32 {
+        match self {
+            ErrorKind::Custom(custom) => custom.exit_code,
+            _ => 1,
+        }
+    }
+
+    /// Built-in kinds followed by the ones the app registered
+    pub fn all() -> Vec<ErrorKind> {
+        let custom = CUSTOM_KINDS.read().unwrap_or_else(|e| e.into_inner());
+        Self::ALL
+            .iter()
+            .copied()
+            .chain(custom.iter().map(|kind| ErrorKind::Custom(kind)))
+            .collect()
+    }
+
+    /// Look a kind up by its code, e.g. `E712`
+    pub fn from_code(code: &str) -> Option<ErrorKind> {
+        Self::all()
+            .into_iter()
+            .find(|kind| kind.code().eq_ignore_ascii_case(code))
+    }
+
+    /// Add an app error kind; its code must be in `CUSTOM_CODES` and its code and name unused
+    ///
+    /// Register kinds once at startup, before the processor runs, so `explain`, docs and the
+    /// schema list them.
+    pub fn register(kind: CustomKind) -> Result<ErrorKind, TermenuError> {
+        let reject = |issue: String| {
+            Err(TermenuError::framework_forbidden_error(Some(json!({
+                "issue": issue
+            }))))
+        };
+
+        let number = kind
+            .code
+            .strip_prefix('E')
+            .filter(|digits| digits.len() == 3)
+            .and_then(|digits| digits.parse::<u16>().ok());
+        match number {
+            Some(number) if CUSTOM_CODES.contains(&number) => {}
+            _ => {
+                return reject(format!(
+                    "Error code '{}' is outside the app range E{}–E{}",
+                    kind.code,
+                    CUSTOM_CODES.start(),
+                    CUSTOM_CODES.end()
+                ));
+            }
+        }
+        if !(1..=255).contains(&kind.exit_code) {
+            return reject(format!(
+                "Exit code {} of {} must be between 1 and 255",
+                kind.exit_code, kind.code
+            ));
+        }
+
+        let mut custom = CUSTOM_KINDS.write().unwrap_or_else(|e| e.into_inner());
+        let taken = Self::ALL
+            .iter()
+            .copied()
+            .chain(custom.iter().map(|kind| ErrorKind::Custom(kind)));
+        for existing in taken {
+            if existing.code() == kind.code || existing.name() == kind.name {
+                return reject(format!(
+                    "Error kind {} {} collides with {} {}",
+                    kind.code,
+                    kind.name,
+                    existing.code(),
+                    existing.name()
+                ));
+            }
+        }
+
+        // Kinds live for the rest of the run, which lets `ErrorKind` stay `Copy`
+        let kind: &'static CustomKind = Box::leak(Box::new(kind));
+        custom.push(kind);
+        Ok(ErrorKind::Custom(kind))
+    }
+}
+
+/// An app-defined error kind, see `ErrorKind::register`
+#[derive(Debug)]
+pub struct CustomKind {
+    code: String,
+    name: String,
+    message: String,
+    exit_code: i32,
+    explanation: Explanation,
+}
+
+impl CustomKind {
+    /// `code` like `E801`, `name` like `QuotaExceededError`, `message` a one-line summary
+    pub fn new(code: &str, name: &str, message: &str) -> Self {
+        Self {
+            code: code.to_uppercase(),
+            name: name.to_string(),
+            message: message.to_string(),
+            exit_code: 1,
+            explanation: Explanation::new(name, message),
+        }
+    }
+
+    /// Exit with `code` instead of 1 when a command fails with this kind
+    pub fn exit_code(mut self, code: i32) -> Self {
+        self.exit_code = code;
+        self
+    }
+
+    /// Common cause, shown by `explain`
+    pub fn cause(mut self, cause: &str) -> Self {
+        self.explanation = self.explanation.cause(cause);
+        self
+    }
+
+    /// Suggested fix, shown by `explain`
+    pub fn remedy(mut self, remedy: &str) -> Self {
+        self.explanation = self.explanation.remedy(remedy);
+        self
+    }
+
+    pub fn explanation(&self) -> &Explanation {
+        &self.explanation
+    }
+}
+
+// Codes are unique once registered, so they identify the kind
+impl PartialEq for CustomKind {
+    fn eq(&self, other: &Self) -> bool {
+        self.code == other.code
+    }
+}
+
+impl Eq for CustomKind {}
+
+impl Hash for CustomKind {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.code.hash(state);
+    }
+}
+
+/// Underlying error kept for `Error::source`
+pub type BoxedSource = Box<dyn Error + Send + Sync + 'static>;
 
 #[allow(dead_code)]
 #[derive(Debug)]
 pub enum TermenuError {
     Exception {
-        code: String,
-        name: String,
+        kind: ErrorKind,
         data: Option<Value>,
+        source: Option<BoxedSource>,
+        /// Suggested fix, see `with_hint`
+        hint: Option<Box<str>>,
+        /// Documentation link, see `with_docs`
+        docs: Option<Box<str>>,
+        /// Index of the offending command line argument, see `at_argument`
+        argument: Option<usize>,
     },
     Io(std::io::Error),
 }
 
 #[allow(dead_code)]
 impl TermenuError {
-    // Framework Errors (<011)
-    pub fn framework_unknown_error(data: Option<Value>) -> Self {
+    /// Build an error of `kind`; `data` usually carries an `issue` string
+    pub fn new(kind: ErrorKind, data: Option<Value>) -> Self {
         TermenuError::Exception {
-            code: "E011".into(),
-            name: "FrameworkError".into(),
+            kind,
             data,
+            source: None,
+            hint: None,
+            docs: None,
+            argument: None,
         }
     }
-    pub fn framework_forbidden_error(data: Option<Value>) -> Self {
+
+    /// Error of `kind` caused by `source`, with the source's message as the issue
+    pub fn caused_by(kind: ErrorKind, issue: &str, source: impl Into<BoxedSource>) -> Self {
         TermenuError::Exception {
-            code: "E011".into(),
-            name: "FrameworkForbiddenError".into(),
-            data,
+            kind,
+            data: Some(json!({ "issue": issue })),
+            source: Some(source.into()),
+            hint: None,
+            docs: None,
+            argument: None,
         }
     }
-    pub fn framework_resource_error(data: Option<Value>) -> Self {
-        TermenuError::Exception {
-            code: "E011".into(),
-            name: "FrameworkForbiddenError".into(),
-            data,
+
+    /// Prefix the issue with what was being done, e.g. `fetching quote: Request failed: ...`
+    ///
+    /// Only the `issue` key of `data` changes; data that isn't an object moves under `value`.
+    pub fn context(self, context: &str) -> Self {
+        let issue = self
+            .issue()
+            .or_else(|| self.source().map(|e| e.to_string()))
+            .unwrap_or_else(|| self.kind().message().to_string());
+        let issue = json!(format!("{}: {}", context, issue));
+        let mut error = self.into_exception();
+        if let TermenuError::Exception { data, .. } = &mut error {
+            *data = Some(match data.take() {
+                Some(Value::Object(mut map)) => {
+                    map.insert("issue".to_string(), issue);
+                    Value::Object(map)
+                }
+                None | Some(Value::Null) => json!({ "issue": issue }),
+                Some(value) => json!({ "issue": issue, "value": value }),
+            });
+        }
+        error
+    }
+
+    /// Suggest a fix, shown below the error
+    pub fn with_hint(self, text: &str) -> Self {
+        let mut error = self.into_exception();
+        if let TermenuError::Exception { hint, .. } = &mut error {
+            *hint = Some(text.into());
+        }
+        error
+    }
+
+    /// Link to documentation about the error
+    pub fn with_docs(self, url: &str) -> Self {
+        let mut error = self.into_exception();
+        if let TermenuError::Exception { docs, .. } = &mut error {
+            *docs = Some(url.into());
+        }
+        error
+    }
+
+    /// Point at the offending command line argument; 0 is the program name
+    pub fn at_argument(self, index: usize) -> Self {
+        let mut error = self.into_exception();
+        if let TermenuError::Exception { argument, .. } = &mut error {
+            *argument = Some(index);
+        }
+        error
+    }
+
+    pub fn hint(&self) -> Option<&str> {
+        match self {
+            TermenuError::Exception { hint, .. } => hint.as_deref(),
+            TermenuError::Io(_) => None,
         }
     }
 
+    pub fn docs(&self) -> Option<&str> {
+        match self {
+            TermenuError::Exception { docs, .. } => docs.as_deref(),
+            TermenuError::Io(_) => None,
+        }
+    }
+
+    pub fn argument(&self) -> Option<usize> {
+        match self {
+            TermenuError::Exception { argument, .. } => *argument,
+            TermenuError::Io(_) => None,
+        }
+    }
+
+    /// I/O errors as exceptions, with the kind and issue of the `From` conversion
+    fn into_exception(self) -> Self {
+        match self {
+            TermenuError::Io(err) => TermenuError::from(err),
+            exception => exception,
+        }
+    }
+
+    /// Kind of the error; I/O errors map as in the `From<io::Error>` conversion
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            TermenuError::Exception { kind, .. } => *kind,
+            TermenuError::Io(err) => io_kind(err),
+        }
+    }
+
+    pub fn code(&self) -> &'static str {
+        self.kind().code()
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.kind().name()
+    }
+
+    pub fn exit_code(&self) -> i32 {
+        self.kind().exit_code()
+    }
+
+    /// The issue, or the kind's default message when there is none
+    pub fn message(&self) -> String {
+        self.issue()
+            .unwrap_or_else(|| self.kind().message().to_string())
+    }
+
+    /// The `issue` given when the error was raised, if any
+    pub fn issue(&self) -> Option<String> {
+        match self {
+            TermenuError::Exception { data, .. } => data
+                .as_ref()
+                .and_then(|d| d.get("issue"))
+                .and_then(|v| v.as_str())
+                .map(str::to_string),
+            TermenuError::Io(err) => Some(err.to_string()),
+        }
+    }
+
+    // Framework Errors (<011)
+    pub fn framework_unknown_error(data: Option<Value>) -> Self {
+        Self::new(ErrorKind::Framework, data)
+    }
+    pub fn framework_forbidden_error(data: Option<Value>) -> Self {
+        Self::new(ErrorKind::FrameworkForbidden, data)
+    }
+    pub fn framework_resource_error(data: Option<Value>) -> Self {
+        Self::new(ErrorKind::FrameworkResource, data)
+    }
+
     /// Input Errors (<111)
     pub fn input_unknown_error(data: Option<Value>) -> Self {
-        TermenuError::Exception {
-            code: "E111".into(),
-            name: "InputError".into(),
-            data,
-        }
+        Self::new(ErrorKind::Input, data)
     }
     pub fn input_missing_error(data: Option<Value>) -> Self {
-        TermenuError::Exception {
-            code: "E112".into(),
-            name: "InputMissingError".into(),
-            data,
-        }
+        Self::new(ErrorKind::InputMissing, data)
     }
 
     /// Connection Errors (<611)
     pub fn connection_unknown_error(data: Option<Value>) -> Self {
-        TermenuError::Exception {
-            code: "E611".into(),
-            name: "ConnectionError".into(),
-            data,
-        }
+        Self::new(ErrorKind::Connection, data)
     }
     pub fn connection_timeout_error(data: Option<Value>) -> Self {
-        TermenuError::Exception {
-            code: "E612".into(),
-            name: "ConnectionTimeoutError".into(),
-            data,
-        }
+        Self::new(ErrorKind::ConnectionTimeout, data)
     }
 
     /// Command Errors (<711)
     pub fn command_unknown_error(data: Option<Value>) -> Self {
-        TermenuError::Exception {
-            code: "E711".into(),
-            name: "CommandError".into(),
-            data,
-        }
+        Self::new(ErrorKind::Command, data)
     }
     pub fn invalid_command_error(data: Option<Value>) -> Self {
-        TermenuError::Exception {
-            code: "E712".into(),
-            name: "InvalidCommandError".into(),
-            data,
-        }
+        Self::new(ErrorKind::InvalidCommand, data)
     }
     pub fn command_timeout_error(data: Option<Value>) -> Self {
-        TermenuError::Exception {
-            code: "E713".into(),
-            name: "CommandTimeoutError".into(),
-            data,
-        }
+        Self::new(ErrorKind::CommandTimeout, data)
     }
 }
 
+/// Plain `[code] Name: issue`, safe for logs and files; see `styled` for terminals
 impl fmt::Display for TermenuError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            TermenuError::Exception { code, name, data } => {
-                let code_colored: ColoredString = code.red().bold();
-                let name_colored: ColoredString = name.yellow().bold();
-
-                if let Some(d) = data {
-                    let issue = d
-                        .get("issue")
-                        .and_then(|v| v.as_str())
-                        .unwrap_or("<no issue field>");
-
-                    write!(f, "[{}] {}: {}", code_colored, name_colored, issue.cyan())
-                } else {
-                    write!(f, "[{}] {}", code_colored, name_colored)
-                }
+            TermenuError::Exception { kind, .. } => {
+                write!(f, "[{}] {}: {}", kind.code(), kind.name(), self.message())
             }
-            TermenuError::Io(err) => write!(f, "{}", err.to_string().red()),
+            TermenuError::Io(err) => write!(f, "{}", err),
         }
     }
 }
@@ -122,7 +489,166 @@ impl Error for TermenuError {
     fn source(&self) -> Option<&(dyn Error + 'static)> {
         match self {
             TermenuError::Io(e) => Some(e),
-            _ => None,
+            TermenuError::Exception { source, .. } => {
+                source.as_deref().map(|e| e as &(dyn Error + 'static))
+            }
+        }
+    }
+}
+
+/// Error kind for an I/O error: timeouts and connection failures as connection errors,
+/// bad input as input errors, anything else as a resource error
+fn io_kind(err: &io::Error) -> ErrorKind {
+    match err.kind() {
+        io::ErrorKind::TimedOut => ErrorKind::ConnectionTimeout,
+        io::ErrorKind::ConnectionRefused
+        | io::ErrorKind::ConnectionReset
+        | io::ErrorKind::ConnectionAborted
+        | io::ErrorKind::NotConnected => ErrorKind::Connection,
+        io::ErrorKind::InvalidInput | io::ErrorKind::InvalidData => ErrorKind::Input,
+        _ => ErrorKind::FrameworkResource,
+    }
+}
+
+impl From<io::Error> for TermenuError {
+    fn from(err: io::Error) -> Self {
+        let issue = err.to_string();
+        Self::caused_by(io_kind(&err), &issue, err)
+    }
+}
+
+impl From<reqwest::Error> for TermenuError {
+    fn from(err: reqwest::Error) -> Self {
+        let (kind, issue) = if err.is_timeout() {
+            (ErrorKind::ConnectionTimeout, "Request timed out")
+        } else if err.is_decode() {
+            (ErrorKind::Input, "Failed to decode response")
+        } else {
+            (ErrorKind::Connection, "Request failed")
+        };
+        let issue = format!("{}: {}", issue, err);
+        Self::caused_by(kind, &issue, err)
+    }
+}
+
+impl From<serde_json::Error> for TermenuError {
+    fn from(err: serde_json::Error) -> Self {
+        let kind = match err.classify() {
+            serde_json::error::Category::Io => ErrorKind::FrameworkResource,
+            _ => ErrorKind::Input,
+        };
+        let issue = format!("Invalid JSON: {}", err);
+        Self::caused_by(kind, &issue, err)
+    }
+}
+
+impl From<toml::de::Error> for TermenuError {
+    fn from(err: toml::de::Error) -> Self {
+        let issue = format!("Invalid TOML: {}", err.message());
+        Self::caused_by(ErrorKind::Input, &issue, err)
+    }
+}
+
+/// `.context("...")` on any result whose error converts into a `TermenuError`
+pub trait ErrorContext<T> {
+    /// Convert the error and prefix its issue with `context`
+    fn context(self, context: &str) -> Result<T, TermenuError>;
+
+    /// Like `context`, but only builds the text on failure
+    fn with_context<F: FnOnce() -> String>(self, context: F) -> Result<T, TermenuError>;
+}
+
+impl<T, E: Into<TermenuError>> ErrorContext<T> for Result<T, E> {
+    fn context(self, context: &str) -> Result<T, TermenuError> {
+        self.map_err(|e| e.into().context(context))
+    }
+
+    fn with_context<F: FnOnce() -> String>(self, context: F) -> Result<T, TermenuError> {
+        self.map_err(|e| e.into().context(&context()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_error_codes_and_names_are_unique() {
+        let mut codes = HashSet::new();
+        let mut names = HashSet::new();
+        for kind in ErrorKind::ALL {
+            assert!(codes.insert(kind.code()), "duplicate code {}", kind.code());
+            assert!(names.insert(kind.name()), "duplicate name {}", kind.name());
+            assert_eq!(ErrorKind::from_code(kind.code()), Some(*kind));
+        }
+    }
+
+    #[test]
+    fn test_constructors_keep_their_kinds() {
+        assert_eq!(
+            TermenuError::framework_resource_error(None).name(),
+            "FrameworkResourceError"
+        );
+        assert_eq!(TermenuError::invalid_command_error(None).code(), "E712");
+        assert_eq!(
+            TermenuError::input_missing_error(None).to_string(),
+            "[E112] InputMissingError: A required input is missing."
+        );
+        assert_eq!(
+            TermenuError::input_missing_error(None).kind().category(),
+            ErrorCategory::Input
+        );
+    }
+
+    #[test]
+    fn test_custom_kinds_use_the_app_range_without_collisions() {
+        let quota = ErrorKind::register(
+            CustomKind::new("E850", "QuotaExceededError", "The daily quota is used up.")
+                .exit_code(3)
+                .remedy("Wait a day."),
+        )
+        .unwrap();
+        let error = TermenuError::new(quota, None);
+        assert_eq!(error.code(), "E850");
+        assert_eq!(error.exit_code(), 3);
+        assert_eq!(quota.category(), ErrorCategory::App);
+        assert_eq!(ErrorKind::from_code("e850"), Some(quota));
+
+        for rejected in [
+            CustomKind::new("E850", "OtherError", "Same code."),
+            CustomKind::new("E851", "QuotaExceededError", "Same name."),
+            CustomKind::new("E851", "InputError", "Built-in name."),
+            CustomKind::new("E712", "MyInvalidError", "Built-in range."),
+            CustomKind::new("E1000", "TooLongError", "Not three digits."),
+            CustomKind::new("E852", "NoExitError", "Exit code 0.").exit_code(0),
+        ] {
+            assert!(ErrorKind::register(rejected).is_err());
         }
     }
+
+    #[test]
+    fn test_conversions_keep_source_and_context() {
+        let parsed: Result<Value, _> = serde_json::from_str("{");
+        let error = parsed.context("reading response").unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::Input);
+        assert!(
+            error
+                .issue()
+                .unwrap()
+                .starts_with("reading response: Invalid JSON:")
+        );
+        assert!(error.source().unwrap().is::<serde_json::Error>());
+
+        let missing = io::Error::new(io::ErrorKind::NotFound, "no such file");
+        let error = TermenuError::from(missing).context("loading config");
+        assert_eq!(error.code(), "E013");
+        assert_eq!(error.issue().unwrap(), "loading config: no such file");
+        assert_eq!(error.source().unwrap().to_string(), "no such file");
+
+        let timeout = io::Error::new(io::ErrorKind::TimedOut, "timed out");
+        let error = TermenuError::Io(timeout).context("fetching quote");
+        assert_eq!(error.kind(), ErrorKind::ConnectionTimeout);
+        assert_eq!(error.issue().unwrap(), "fetching quote: timed out");
+    }
 }
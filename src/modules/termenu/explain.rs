@@ -0,0 +1,217 @@
+use colored::Colorize;
+use serde_json::json;
+
+use super::app_info::AppInfo;
+use super::context::Context;
+use super::core::Termenu;
+use super::exceptions::{ErrorKind, TermenuError};
+use super::layout;
+use super::option::Candidate;
+
+/// Long form help for an error code, shown by `explain <code>`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Explanation {
+    pub name: String,
+    pub description: String,
+    pub causes: Vec<String>,
+    pub remedies: Vec<String>,
+}
+
+impl Explanation {
+    pub fn new(name: &str, description: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            description: description.to_string(),
+            causes: Vec::new(),
+            remedies: Vec::new(),
+        }
+    }
+
+    pub fn cause(mut self, cause: &str) -> Self {
+        self.causes.push(cause.to_string());
+        self
+    }
+
+    pub fn remedy(mut self, remedy: &str) -> Self {
+        self.remedies.push(remedy.to_string());
+        self
+    }
+
+    /// Explanation for `code`, matched case-insensitively
+    ///
+    /// Apps explain their own codes through the `CustomKind` they register with
+    /// `ErrorKind::register`.
+    pub fn find(code: &str) -> Option<Explanation> {
+        ErrorKind::from_code(code).map(builtin)
+    }
+
+    /// Every known code with its explanation, sorted by code
+    pub fn all() -> Vec<(String, Explanation)> {
+        let mut all: Vec<(String, Explanation)> = ErrorKind::all()
+            .into_iter()
+            .map(|kind| (kind.code().to_string(), builtin(kind)))
+            .collect();
+        all.sort_by(|a, b| a.0.cmp(&b.0));
+        all
+    }
+
+    fn print(&self, code: &str, width: usize) {
+        println!("{} {}", code.red().bold(), self.name.yellow().bold());
+        println!("{}", layout::paragraph(&self.description, 2, width));
+        for (heading, items) in [
+            ("Common causes:", &self.causes),
+            ("What to do:", &self.remedies),
+        ] {
+            if items.is_empty() {
+                continue;
+            }
+            println!("\n{}", heading.bold());
+            for item in items {
+                let text = layout::paragraph(item, 4, width);
+                // Replace the indent of the first line with a bullet
+                println!("  •{}", &text[3.min(text.len())..]);
+            }
+        }
+    }
+}
+
+/// Explanation of an error kind, built-in or registered by the app
+fn builtin(kind: ErrorKind) -> Explanation {
+    let explanation = Explanation::new(kind.name(), kind.message());
+    match kind {
+        ErrorKind::Custom(custom) => custom.explanation().clone(),
+        ErrorKind::Framework => explanation
+            .cause("A bug in Termenu or in the app, such as data that failed to serialize.")
+            .cause("The app's commands failed validation, e.g. a duplicate name or option.")
+            .remedy("Run the command again with the same arguments to see if it is reproducible.")
+            .remedy("Report it together with the output of `version --format=json`."),
+        ErrorKind::FrameworkForbidden => explanation
+            .cause("The command is limited to certain builds, e.g. `developer` in release builds.")
+            .remedy("Use a debug build (`cargo run -- <command>`) for development-only commands."),
+        ErrorKind::FrameworkResource => explanation
+            .cause("Shared state asked for with `ctx.state::<T>()` was never registered.")
+            .cause("A file or directory could not be read or written, e.g. the job state directory.")
+            .remedy("Register the resource with `State::register` before starting the processor.")
+            .remedy("Check that the path in the message exists and is writable."),
+        ErrorKind::Input => explanation
+            .cause("An option was given a value outside its possible values.")
+            .cause("A value could not be parsed, such as an interval, cron expression or JSON response.")
+            .remedy("Check `help <command>` for the accepted values of each option."),
+        ErrorKind::InputMissing => explanation
+            .cause("A required option or argument was left out.")
+            .cause("An option that needs a value was written without one, e.g. `--add` instead of `--add=name`.")
+            .remedy("Pass values as `--name=value`; `help <command>` shows which options need one."),
+        ErrorKind::Connection => explanation
+            .cause("The network is unreachable, or a host name did not resolve.")
+            .cause("The remote service returned an error or is down.")
+            .remedy("Check your connection and any proxy settings, then try again."),
+        ErrorKind::ConnectionTimeout => explanation
+            .cause("The remote service is slow or the network is congested.")
+            .remedy("Try again later, or from a faster connection."),
+        ErrorKind::Command => explanation
+            .cause("The command's own work failed; the message says why.")
+            .cause("A scheduled command reached its --max-failures limit.")
+            .cause("Generated docs checked with `docs --check` are out of date.")
+            .remedy("Read the issue in the message; it names what went wrong."),
+        ErrorKind::InvalidCommand => explanation
+            .cause("The command name is misspelled or does not exist.")
+            .cause("An option is not known to the command, or a flag was given a value.")
+            .cause("More positional arguments were given than the command accepts.")
+            .remedy("Run `help` to list commands and `help <command>` to list its options."),
+        ErrorKind::CommandTimeout => explanation
+            .cause("The command took longer than it is allowed to run.")
+            .remedy("Try again, or run it in the background with --background."),
+    }
+}
+
+/// Built-in `explain` command
+pub fn register() -> Termenu {
+    let mut command = Termenu::new_command_with_context(
+        "explain",
+        "Explain an error code, its common causes and what to do.",
+        |ctx: &Context| {
+            let width = layout::terminal_width();
+
+            if ctx.has_option("--list") {
+                let all = Explanation::all();
+                let columns =
+                    layout::Columns::new(2, all.iter().map(|(code, _)| code.as_str()), width);
+                for (code, explanation) in &all {
+                    let summary = format!("{}: {}", explanation.name, explanation.description);
+                    println!("{}", columns.row(code, code.red().bold(), &summary));
+                }
+                return Ok(());
+            }
+
+            let code = ctx.option("code").ok_or_else(|| {
+                TermenuError::input_missing_error(Some(json!({
+                    "issue": format!(
+                        "Error code is required, e.g. `{} explain E712`. Use --list to see all codes.",
+                        AppInfo::current().name
+                    )
+                })))
+            })?;
+            let explanation = Explanation::find(code).ok_or_else(|| {
+                TermenuError::input_unknown_error(Some(json!({
+                    "issue": format!(
+                        "Unknown error code '{}'. Use `{} explain --list` to see all codes.",
+                        code,
+                        AppInfo::current().name
+                    )
+                })))
+            })?;
+            explanation.print(&code.to_uppercase(), width);
+            Ok(())
+        },
+    );
+
+    command
+        .add_argument("code", "Error code to explain, e.g. E712.")
+        .complete_with(|_partial| {
+            Explanation::all()
+                .iter()
+                .map(|(code, explanation)| Candidate::with_description(code, &explanation.name))
+                .collect()
+        });
+    command
+        .add_option("--list", "List every error code.")
+        .flag();
+    command
+        .add_example("explain E712", "Explain InvalidCommandError.")
+        .add_example("explain --list", "List all codes with a one-line summary.")
+        .see_also(&["help"])
+        .error_codes(&["E112", "E111"]);
+
+    command
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::termenu::exceptions::CustomKind;
+
+    #[test]
+    fn test_every_builtin_code_is_explained_and_apps_can_add_codes() {
+        for kind in ErrorKind::ALL {
+            let explanation = Explanation::find(&kind.code().to_lowercase()).unwrap();
+            assert!(
+                !explanation.causes.is_empty(),
+                "{} has no causes",
+                kind.code()
+            );
+            assert!(
+                !explanation.remedies.is_empty(),
+                "{} has no remedies",
+                kind.code()
+            );
+        }
+
+        ErrorKind::register(
+            CustomKind::new("E901", "QuotaError", "The daily quota is used up.")
+                .remedy("Wait a day."),
+        )
+        .unwrap();
+        assert_eq!(Explanation::find("e901").unwrap().remedies, ["Wait a day."]);
+        assert!(Explanation::all().iter().any(|(code, _)| code == "E901"));
+    }
+}
@@ -0,0 +1,228 @@
+use serde_json::json;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use super::exceptions::TermenuError;
+use super::report::ErrorFormat;
+use super::schedule::{CronExpr, Schedule, ScheduleConfig, parse_interval};
+use super::watch::WatchConfig;
+
+/// Options accepted before the command name, e.g. `termenu --watch=src/ inspire`
+pub const GLOBAL_OPTIONS: &[(&str, &str)] = &[
+    (
+        "--watch",
+        "Re-run the command when a path changes (comma separated, repeatable).",
+    ),
+    (
+        "--debounce",
+        "Milliseconds to wait for changes to settle in watch mode (default 300).",
+    ),
+    ("--clear", "Clear the screen between watch mode runs."),
+    (
+        "--every",
+        "Re-run the command on a fixed interval (e.g. 30s, 5m, 1h).",
+    ),
+    (
+        "--cron",
+        "Re-run the command on a cron schedule (e.g. --cron=\"0 9 * * *\").",
+    ),
+    (
+        "--max-failures",
+        "Stop a scheduled command after N consecutive failed runs.",
+    ),
+    (
+        "--background",
+        "Detach and run the command as a background job (see `jobs`).",
+    ),
+    ("--version", "Print version and build details, then exit."),
+    ("--help", "Show this help (also -h), then exit."),
+    (
+        "--output",
+        "Error format on stderr: text (default) or json.",
+    ),
+];
+
+/// Global options that never take a value
+pub const GLOBAL_FLAGS: &[&str] = &["--clear", "--background", "--version", "--help"];
+
+/// Parsed global options
+#[derive(Debug, Default)]
+pub struct GlobalOptions {
+    pub watch: Option<WatchConfig>,
+    pub schedule: Option<ScheduleConfig>,
+    pub background: bool,
+    pub version: bool,
+    pub help: bool,
+}
+
+impl GlobalOptions {
+    /// Split leading global options from the command and its own arguments
+    ///
+    /// Returns the parsed globals and the remaining arguments, starting at the command name.
+    pub fn parse(args: &[String]) -> Result<(Self, &[String]), TermenuError> {
+        let mut watch_paths: Vec<PathBuf> = Vec::new();
+        let mut debounce: Option<Duration> = None;
+        let mut clear = false;
+        let mut schedule: Option<Schedule> = None;
+        let mut max_failures: Option<u32> = None;
+        let mut background = false;
+        let mut version = false;
+        let mut help = false;
+
+        let mut index = 0;
+        while index < args.len() && (args[index].starts_with("--") || args[index] == "-h") {
+            let arg = &args[index];
+            let (key, value) = match arg.split_once('=') {
+                Some((key, value)) => (key, Some(value)),
+                None => (arg.as_str(), None),
+            };
+
+            match (key, value) {
+                ("--watch", Some(value)) => watch_paths.extend(
+                    value
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|p| !p.is_empty())
+                        .map(PathBuf::from),
+                ),
+                ("--debounce", Some(value)) => {
+                    let millis = value.parse::<u64>().map_err(|_| {
+                        TermenuError::input_unknown_error(Some(json!({
+                            "issue": format!("Invalid --debounce value: '{}' (expected milliseconds)", value)
+                        })))
+                    })?;
+                    debounce = Some(Duration::from_millis(millis));
+                }
+                ("--clear", None) => clear = true,
+                ("--background", None) => background = true,
+                ("--version", None) => version = true,
+                ("--help" | "-h", None) => help = true,
+                // Selected right away so errors in the globals after it use it too
+                ("--output", Some(value)) => ErrorFormat::select(ErrorFormat::parse(value)?),
+                ("--every" | "--cron", Some(value)) => {
+                    if schedule.is_some() {
+                        return Err(TermenuError::invalid_command_error(Some(json!({
+                            "issue": "Use only one of `--every` and `--cron`."
+                        }))));
+                    }
+                    schedule = Some(if key == "--every" {
+                        Schedule::Every(parse_interval(value)?)
+                    } else {
+                        Schedule::Cron(CronExpr::parse(value)?)
+                    });
+                }
+                ("--max-failures", Some(value)) => {
+                    let max = value.parse::<u32>().ok().filter(|max| *max > 0).ok_or_else(|| {
+                        TermenuError::input_unknown_error(Some(json!({
+                            "issue": format!("Invalid --max-failures value: '{}' (expected a positive number)", value)
+                        })))
+                    })?;
+                    max_failures = Some(max);
+                }
+                (
+                    "--watch" | "--debounce" | "--every" | "--cron" | "--max-failures" | "--output",
+                    None,
+                ) => {
+                    return Err(TermenuError::input_missing_error(Some(json!({
+                        "issue": format!("Global option '{}' requires a value ({}=<value>)", key, key)
+                    }))));
+                }
+                _ => {
+                    return Err(TermenuError::invalid_command_error(Some(json!({
+                        "issue": format!("Unknown global option: '{}'", key)
+                    }))));
+                }
+            }
+            index += 1;
+        }
+
+        let watch = if watch_paths.is_empty() {
+            if debounce.is_some() || clear {
+                return Err(TermenuError::input_missing_error(Some(json!({
+                    "issue": "`--debounce` and `--clear` require `--watch=<path>`."
+                }))));
+            }
+            None
+        } else {
+            let mut config = WatchConfig::new(watch_paths);
+            if let Some(debounce) = debounce {
+                config.debounce = debounce;
+            }
+            config.clear = clear;
+            Some(config)
+        };
+
+        let schedule = match schedule {
+            Some(schedule) => {
+                if watch.is_some() {
+                    return Err(TermenuError::invalid_command_error(Some(json!({
+                        "issue": "`--watch` cannot be combined with `--every` or `--cron`."
+                    }))));
+                }
+                Some(ScheduleConfig {
+                    schedule,
+                    max_failures,
+                })
+            }
+            None if max_failures.is_some() => {
+                return Err(TermenuError::input_missing_error(Some(json!({
+                    "issue": "`--max-failures` requires `--every` or `--cron`."
+                }))));
+            }
+            None => None,
+        };
+
+        Ok((
+            Self {
+                watch,
+                schedule,
+                background,
+                version,
+                help,
+            },
+            &args[index..],
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_watch_options() {
+        let args = args(&[
+            "--watch=src/,Cargo.toml",
+            "--clear",
+            "inspire",
+            "--category=x",
+        ]);
+        let (globals, rest) = GlobalOptions::parse(&args).unwrap();
+        let watch = globals.watch.unwrap();
+        assert_eq!(
+            watch.paths,
+            vec![PathBuf::from("src/"), PathBuf::from("Cargo.toml")]
+        );
+        assert!(watch.clear);
+        assert_eq!(rest, &args[2..]);
+    }
+
+    #[test]
+    fn test_parse_help_flags() {
+        for flag in ["--help", "-h"] {
+            let args = args(&[flag]);
+            let (globals, rest) = GlobalOptions::parse(&args).unwrap();
+            assert!(globals.help);
+            assert!(rest.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_global() {
+        assert!(GlobalOptions::parse(&args(&["--nope", "inspire"])).is_err());
+    }
+}
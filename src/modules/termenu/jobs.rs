@@ -0,0 +1,476 @@
+use chrono::Local;
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use uuid::Uuid;
+
+use super::app_info::AppInfo;
+use super::core::Termenu;
+use super::exceptions::TermenuError;
+use super::option::Candidate;
+
+/// Set on a detached child process so it can report back to its job record
+pub const JOB_ID_ENV: &str = "TERMENU_JOB_ID";
+
+/// Overrides the directory used for job records, logs and crash reports
+pub const STATE_DIR_ENV: &str = "TERMENU_STATE_DIR";
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JobStatus {
+    Running,
+    Succeeded,
+    Failed,
+    Killed,
+    /// The process is gone without having reported a result
+    Lost,
+}
+
+impl JobStatus {
+    fn label(&self) -> &'static str {
+        match self {
+            JobStatus::Running => "running",
+            JobStatus::Succeeded => "succeeded",
+            JobStatus::Failed => "failed",
+            JobStatus::Killed => "killed",
+            JobStatus::Lost => "lost",
+        }
+    }
+}
+
+/// Persisted record of a background job
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobRecord {
+    pub id: String,
+    pub command: String,
+    pub args: Vec<String>,
+    pub pid: u32,
+    pub status: JobStatus,
+    pub started_at: String,
+    pub finished_at: Option<String>,
+    pub error: Option<String>,
+}
+
+/// Per-user directory for the app's state, e.g. `~/.local/state/<app>`
+pub fn state_dir() -> PathBuf {
+    let app = AppInfo::current().name;
+    if let Some(dir) = env::var_os(STATE_DIR_ENV) {
+        PathBuf::from(dir)
+    } else if let Some(dir) = env::var_os("XDG_STATE_HOME") {
+        PathBuf::from(dir).join(&app)
+    } else if let Some(dir) = env::var_os("LOCALAPPDATA") {
+        PathBuf::from(dir).join(&app)
+    } else if let Some(home) = env::var_os("HOME") {
+        PathBuf::from(home).join(".local").join("state").join(&app)
+    } else {
+        env::temp_dir().join(&app)
+    }
+}
+
+/// Directory holding job records (`<id>.json`) and logs (`<id>.log`)
+pub fn jobs_dir() -> PathBuf {
+    state_dir().join("jobs")
+}
+
+fn record_path(id: &str) -> PathBuf {
+    jobs_dir().join(format!("{}.json", id))
+}
+
+fn log_path(id: &str) -> PathBuf {
+    jobs_dir().join(format!("{}.log", id))
+}
+
+fn io_error(action: &str, e: std::io::Error) -> TermenuError {
+    TermenuError::framework_resource_error(Some(json!({
+        "issue": format!("Failed to {}: {}", action, e)
+    })))
+}
+
+fn save(record: &JobRecord) -> Result<(), TermenuError> {
+    let content = serde_json::to_string_pretty(record).map_err(|e| {
+        TermenuError::framework_unknown_error(Some(json!({
+            "issue": format!("Failed to serialize job record: {}", e)
+        })))
+    })?;
+    fs::write(record_path(&record.id), content).map_err(|e| io_error("write job record", e))
+}
+
+fn load(id: &str) -> Result<JobRecord, TermenuError> {
+    let content =
+        fs::read_to_string(record_path(id)).map_err(|e| io_error("read job record", e))?;
+    serde_json::from_str(&content).map_err(|e| {
+        TermenuError::framework_unknown_error(Some(json!({
+            "issue": format!("Corrupt job record '{}': {}", id, e)
+        })))
+    })
+}
+
+/// All job records, oldest first
+pub fn list() -> Result<Vec<JobRecord>, TermenuError> {
+    let dir = jobs_dir();
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut records = Vec::new();
+    for entry in fs::read_dir(&dir).map_err(|e| io_error("read jobs directory", e))? {
+        let path = entry
+            .map_err(|e| io_error("read jobs directory", e))?
+            .path();
+        if path.extension().and_then(|e| e.to_str()) != Some("json") {
+            continue;
+        }
+        if let Some(id) = path.file_stem().and_then(|s| s.to_str())
+            && let Ok(record) = load(id)
+        {
+            records.push(refresh(record));
+        }
+    }
+    records.sort_by(|a, b| a.started_at.cmp(&b.started_at));
+    Ok(records)
+}
+
+/// Find a job by its full id or a unique prefix of it
+pub fn find(id: &str) -> Result<JobRecord, TermenuError> {
+    let matches: Vec<JobRecord> = list()?
+        .into_iter()
+        .filter(|job| job.id.starts_with(id))
+        .collect();
+
+    match matches.len() {
+        1 => Ok(matches.into_iter().next().expect("one match")),
+        0 => Err(TermenuError::input_unknown_error(Some(json!({
+            "issue": format!("No job found with id '{}'", id)
+        })))),
+        _ => Err(TermenuError::input_unknown_error(Some(json!({
+            "issue": format!("Job id '{}' is ambiguous; use more characters", id)
+        })))),
+    }
+}
+
+/// Mark running jobs whose process has disappeared as lost
+fn refresh(mut record: JobRecord) -> JobRecord {
+    // A pid of 0 means the child has not started yet
+    if record.status == JobStatus::Running && record.pid != 0 && !is_alive(record.pid) {
+        record.status = JobStatus::Lost;
+        record
+            .finished_at
+            .get_or_insert_with(|| Local::now().to_rfc3339());
+        let _ = save(&record);
+    }
+    record
+}
+
+fn is_alive(pid: u32) -> bool {
+    #[cfg(unix)]
+    {
+        Command::new("kill")
+            .args(["-0", &pid.to_string()])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .map(|s| s.success())
+            .unwrap_or(false)
+    }
+    #[cfg(windows)]
+    {
+        Command::new("tasklist")
+            .args(["/FI", &format!("PID eq {}", pid), "/NH"])
+            .output()
+            .map(|o| String::from_utf8_lossy(&o.stdout).contains(&pid.to_string()))
+            .unwrap_or(false)
+    }
+}
+
+/// Re-launch the current executable detached, with output going to the job log
+pub fn spawn(args: &[String]) -> Result<JobRecord, TermenuError> {
+    let dir = jobs_dir();
+    fs::create_dir_all(&dir).map_err(|e| io_error("create jobs directory", e))?;
+
+    let id = Uuid::new_v4().to_string();
+    let log = fs::File::create(log_path(&id)).map_err(|e| io_error("create job log", e))?;
+    let log_err = log.try_clone().map_err(|e| io_error("create job log", e))?;
+    let exe = env::current_exe().map_err(|e| io_error("locate current executable", e))?;
+
+    let mut command = Command::new(exe);
+    command
+        .args(args)
+        .env(JOB_ID_ENV, &id)
+        .stdin(Stdio::null())
+        .stdout(log)
+        .stderr(log_err);
+
+    // Keep the job alive when the launching terminal sends Ctrl-C
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        command.process_group(0);
+    }
+
+    // The record exists before the child starts, so the child can always update it
+    let mut record = JobRecord {
+        id,
+        command: args
+            .iter()
+            .find(|a| !a.starts_with("--"))
+            .cloned()
+            .unwrap_or_default(),
+        args: args.to_vec(),
+        pid: 0,
+        status: JobStatus::Running,
+        started_at: Local::now().to_rfc3339(),
+        finished_at: None,
+        error: None,
+    };
+    save(&record)?;
+
+    let child = match command.spawn() {
+        Ok(child) => child,
+        Err(e) => {
+            record.status = JobStatus::Failed;
+            record.error = Some(e.to_string());
+            record.finished_at = Some(Local::now().to_rfc3339());
+            let _ = save(&record);
+            return Err(io_error("start background job", e));
+        }
+    };
+    record.pid = child.id();
+
+    // The child records its own pid too; only fill it in if it has not done so yet
+    if let Ok(mut current) = load(&record.id)
+        && current.pid == 0
+    {
+        current.pid = record.pid;
+        save(&current)?;
+    }
+    Ok(record)
+}
+
+/// Called inside the detached process when it starts
+pub fn attach(id: &str) {
+    if let Ok(mut record) = load(id) {
+        record.pid = std::process::id();
+        let _ = save(&record);
+    }
+}
+
+/// Called inside the detached process once the command has finished
+pub fn finish(id: &str, result: &Result<(), TermenuError>) {
+    if let Ok(mut record) = load(id)
+        && record.status != JobStatus::Killed
+    {
+        record.status = match result {
+            Ok(()) => JobStatus::Succeeded,
+            Err(_) => JobStatus::Failed,
+        };
+        record.error = result.as_ref().err().map(|e| e.to_string());
+        record.finished_at = Some(Local::now().to_rfc3339());
+        let _ = save(&record);
+    }
+}
+
+fn kill(record: &mut JobRecord) -> Result<(), TermenuError> {
+    if record.status != JobStatus::Running {
+        return Err(TermenuError::input_unknown_error(Some(json!({
+            "issue": format!("Job {} is not running ({})", record.id, record.status.label())
+        }))));
+    }
+    // Signalling pid 0 would hit our own process group
+    if record.pid == 0 {
+        return Err(TermenuError::input_unknown_error(Some(json!({
+            "issue": format!("Job {} has no process yet, so it cannot be killed", record.id)
+        }))));
+    }
+
+    #[cfg(unix)]
+    let status = Command::new("kill")
+        .args(["-TERM", &record.pid.to_string()])
+        .status();
+    #[cfg(windows)]
+    let status = Command::new("taskkill")
+        .args(["/PID", &record.pid.to_string(), "/T", "/F"])
+        .status();
+
+    match status {
+        Ok(s) if s.success() => {
+            record.status = JobStatus::Killed;
+            record.finished_at = Some(Local::now().to_rfc3339());
+            save(record)
+        }
+        Ok(_) => Err(TermenuError::command_unknown_error(Some(json!({
+            "issue": format!("Failed to kill job {} (pid {})", record.id, record.pid)
+        })))),
+        Err(e) => Err(io_error("kill job", e)),
+    }
+}
+
+/// Short local timestamp for display
+fn display_time(timestamp: &str) -> String {
+    chrono::DateTime::parse_from_rfc3339(timestamp)
+        .map(|t| {
+            t.with_timezone(&Local)
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string()
+        })
+        .unwrap_or_else(|_| timestamp.to_string())
+}
+
+fn status_colored(status: JobStatus) -> colored::ColoredString {
+    match status {
+        JobStatus::Running => status.label().cyan(),
+        JobStatus::Succeeded => status.label().green(),
+        JobStatus::Failed | JobStatus::Lost => status.label().red(),
+        JobStatus::Killed => status.label().yellow(),
+    }
+}
+
+fn print_status(record: &JobRecord) {
+    println!("{:<12} {}", "ID:".bold(), record.id);
+    println!("{:<12} {}", "Status:".bold(), status_colored(record.status));
+    println!("{:<12} {}", "Command:".bold(), record.args.join(" "));
+    println!("{:<12} {}", "PID:".bold(), record.pid);
+    println!(
+        "{:<12} {}",
+        "Started:".bold(),
+        display_time(&record.started_at)
+    );
+    if let Some(finished) = &record.finished_at {
+        println!("{:<12} {}", "Finished:".bold(), display_time(finished));
+    }
+    if let Some(error) = &record.error {
+        println!("{:<12} {}", "Error:".bold(), error);
+    }
+    println!("{:<12} {}", "Log:".bold(), log_path(&record.id).display());
+}
+
+/// Built-in `jobs` command: `jobs list|status|logs|kill [id]`
+pub fn register() -> Termenu {
+    let mut command = Termenu::new_command(
+        "jobs",
+        "Manage background jobs started with --background.",
+        |options| {
+            let action = options
+                .get("action")
+                .and_then(|v| v.as_deref())
+                .unwrap_or("list");
+            let id = options.get("id").and_then(|v| v.as_deref());
+
+            let require_id = || {
+                id.ok_or_else(|| {
+                    TermenuError::input_missing_error(Some(json!({
+                        "issue": format!("Job id is required. Use `jobs {} <id>`.", action)
+                    })))
+                })
+            };
+
+            match action {
+                "list" => {
+                    let jobs = list()?;
+                    if jobs.is_empty() {
+                        println!("No background jobs.");
+                        return Ok(());
+                    }
+                    println!(
+                        "{}",
+                        format!(
+                            "{:<10} {:<10} {:<8} {:<20} {}",
+                            "ID", "STATUS", "PID", "STARTED", "COMMAND"
+                        )
+                        .bold()
+                    );
+                    for job in jobs {
+                        println!(
+                            "{:<10} {:<10} {:<8} {:<20} {}",
+                            job.id.get(..8).unwrap_or(&job.id),
+                            status_colored(job.status),
+                            job.pid,
+                            display_time(&job.started_at),
+                            job.args.join(" ")
+                        );
+                    }
+                    Ok(())
+                }
+                "status" => {
+                    print_status(&find(require_id()?)?);
+                    Ok(())
+                }
+                "logs" => {
+                    let job = find(require_id()?)?;
+                    let content = fs::read_to_string(log_path(&job.id))
+                        .map_err(|e| io_error("read job log", e))?;
+                    print!("{}", content);
+                    Ok(())
+                }
+                "kill" => {
+                    let mut job = find(require_id()?)?;
+                    kill(&mut job)?;
+                    println!("{} Killed job {}", "✔".green(), job.id);
+                    Ok(())
+                }
+                other => Err(TermenuError::invalid_command_error(Some(json!({
+                    "issue": format!(
+                        "Unknown jobs action '{}'. Use list, status, logs or kill.",
+                        other
+                    )
+                })))),
+            }
+        },
+    );
+
+    command
+        .add_argument("action", "What to do with the jobs.")
+        .choices(&["list", "status", "logs", "kill"])
+        .default_value("list");
+    command
+        .add_argument("id", "Job id, or a unique prefix of it.")
+        .complete_with(|_partial| {
+            list()
+                .unwrap_or_default()
+                .iter()
+                .rev()
+                .map(|job| {
+                    Candidate::with_description(
+                        job.id.get(..8).unwrap_or(&job.id),
+                        &format!("{} {}", job.status.label(), job.args.join(" ")),
+                    )
+                })
+                .collect()
+        });
+
+    command
+        .long_description(
+            "Jobs are started by putting --background before the command name. Their output goes to a log file in the state directory (TERMENU_STATE_DIR overrides it) and ids can be shortened to any unique prefix.",
+        )
+        .add_example("--background --every=5m inspire", "Fetch a quote every five minutes in the background.")
+        .add_example("jobs status 1a2b3c4d", "Show the status and log path of one job.")
+        .add_example("jobs logs 1a2b3c4d", "Print the output of a job.")
+        .add_example("jobs kill 1a2b3c4d", "Stop a running job.")
+        .error_codes(&["E112", "E111", "E013", "E011", "E711"]);
+
+    command
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kill_refuses_a_job_without_a_process() {
+        let mut record = JobRecord {
+            id: "short".to_string(),
+            command: "inspire".to_string(),
+            args: Vec::new(),
+            pid: 0,
+            status: JobStatus::Running,
+            started_at: Local::now().to_rfc3339(),
+            finished_at: None,
+            error: None,
+        };
+        assert!(kill(&mut record).is_err());
+        assert_eq!(record.status, JobStatus::Running);
+    }
+}
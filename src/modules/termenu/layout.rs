@@ -0,0 +1,162 @@
+use colored::ColoredString;
+use std::env;
+use terminal_size::{Width, terminal_size};
+
+/// Width used when stdout is not a terminal and `COLUMNS` is unset
+pub const DEFAULT_WIDTH: usize = 80;
+/// Narrowest text column before descriptions move below their label
+pub const MIN_TEXT_WIDTH: usize = 24;
+/// Labels longer than this don't widen the column; their text starts on the next line
+pub const MAX_LABEL_WIDTH: usize = 28;
+
+/// Width to lay help out in: the terminal, then `COLUMNS`, then `DEFAULT_WIDTH`
+pub fn terminal_width() -> usize {
+    if let Some((Width(width), _)) = terminal_size() {
+        return width as usize;
+    }
+    env::var("COLUMNS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|&w: &usize| w > 0)
+        .unwrap_or(DEFAULT_WIDTH)
+}
+
+/// Greedy word wrap; words longer than `width` get a line of their own
+pub fn wrap(text: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let mut lines = Vec::new();
+
+    for paragraph in text.lines() {
+        let mut line = String::new();
+        for word in paragraph.split_whitespace() {
+            let len = line.chars().count();
+            if len > 0 && len + 1 + word.chars().count() > width {
+                lines.push(std::mem::take(&mut line));
+            }
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(word);
+        }
+        lines.push(line);
+    }
+    if lines.is_empty() {
+        lines.push(String::new());
+    }
+    lines
+}
+
+/// Wrap `text` and indent every line by `indent` spaces
+pub fn paragraph(text: &str, indent: usize, width: usize) -> String {
+    let pad = " ".repeat(indent);
+    wrap(text, width.saturating_sub(indent))
+        .iter()
+        .map(|line| {
+            if line.is_empty() {
+                String::new()
+            } else {
+                format!("{}{}", pad, line)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Two aligned columns: a styled label and wrapped text with a hanging indent
+pub struct Columns {
+    indent: usize,
+    label_width: usize,
+    width: usize,
+}
+
+impl Columns {
+    /// Size the label column to the longest of `labels`, within `MAX_LABEL_WIDTH`
+    pub fn new<'a>(indent: usize, labels: impl IntoIterator<Item = &'a str>, width: usize) -> Self {
+        let longest = labels
+            .into_iter()
+            .map(|label| label.chars().count())
+            .filter(|&len| len <= MAX_LABEL_WIDTH)
+            .max()
+            .unwrap_or(0);
+        Self {
+            indent,
+            label_width: longest,
+            width,
+        }
+    }
+
+    /// Column where the text starts
+    fn text_start(&self) -> usize {
+        self.indent + self.label_width + 2
+    }
+
+    /// One row; `label` is the plain text used for measuring, `styled` what gets printed
+    pub fn row(&self, label: &str, styled: ColoredString, text: &str) -> String {
+        let start = self.text_start();
+        let pad = " ".repeat(self.indent);
+        let label_len = label.chars().count();
+
+        let text_width = self.width.saturating_sub(start);
+        if text_width < MIN_TEXT_WIDTH {
+            // Too narrow for two columns: text goes below the label
+            let below = paragraph(text, self.indent + 4, self.width);
+            return format!("{}{}\n{}", pad, styled, below);
+        }
+
+        let lines = wrap(text, text_width);
+        let hanging = " ".repeat(start);
+        let mut out = if label_len > self.label_width {
+            format!("{}{}\n{}{}", pad, styled, hanging, lines[0])
+        } else {
+            format!(
+                "{}{}{}{}",
+                pad,
+                styled,
+                " ".repeat(self.label_width - label_len + 2),
+                lines[0]
+            )
+        };
+        for line in &lines[1..] {
+            out.push('\n');
+            out.push_str(&hanging);
+            out.push_str(line);
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use colored::Colorize;
+
+    #[test]
+    fn test_wrap_breaks_on_words() {
+        assert_eq!(
+            wrap("the quick brown fox jumps", 10),
+            vec!["the quick", "brown fox", "jumps"]
+        );
+        assert_eq!(
+            wrap("averyveryverylongword ok", 5),
+            vec!["averyveryverylongword", "ok"]
+        );
+    }
+
+    #[test]
+    fn test_rows_align_and_hang() {
+        colored::control::set_override(false);
+        let columns = Columns::new(2, ["--a", "--longer"], 40);
+        assert_eq!(
+            columns.row("--a", "--a".normal(), "short"),
+            "  --a       short"
+        );
+        assert_eq!(
+            columns.row(
+                "--longer",
+                "--longer".normal(),
+                "wraps onto a second line here"
+            ),
+            "  --longer  wraps onto a second line\n            here"
+        );
+    }
+}
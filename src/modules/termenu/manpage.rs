@@ -0,0 +1,345 @@
+use colored::Colorize;
+use serde_json::json;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::app_info::AppInfo;
+use super::context::Context;
+use super::core::{Registry, Termenu};
+use super::deprecation;
+use super::exceptions::{ErrorKind, TermenuError};
+use super::globals::{GLOBAL_FLAGS, GLOBAL_OPTIONS};
+use super::option::CommandOption;
+
+/// Escape text for use in a roff document
+fn roff(text: &str) -> String {
+    let escaped = text.replace('\\', "\\e").replace('-', "\\-");
+    // A leading `.` or `'` would be read as a request
+    escaped
+        .lines()
+        .map(|line| {
+            if line.starts_with('.') || line.starts_with('\'') {
+                format!("\\&{}", line)
+            } else {
+                line.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn title_header(page: &str, info: &AppInfo) -> String {
+    format!(
+        ".TH \"{}\" \"1\" \"{}\" \"{} {}\" \"User Commands\"\n",
+        page.to_uppercase(),
+        env!("TERMENU_BUILD_DATE"),
+        roff(&info.name),
+        roff(&info.version)
+    )
+}
+
+/// Usage label for an option, e.g. `--format=text|json` with the name in bold
+fn option_label(option: &CommandOption) -> String {
+    option.label(
+        |name| format!("\\fB{}\\fR", roff(name)),
+        |value| format!("\\fI{}\\fR", roff(value)),
+    )
+}
+
+/// `.TP` entry for an option or argument
+fn option_entry(label: &str, description: &str, default: Option<&str>) -> String {
+    let mut out = format!(".TP\n{}\n{}\n", label, roff(description));
+    if let Some(default) = default {
+        out.push_str(&format!(".br\nDefault: {}\n", roff(default)));
+    }
+    out
+}
+
+/// Command description with its deprecation notes
+fn command_details(command: &Termenu) -> String {
+    deprecation::annotate(
+        &command.description,
+        command.deprecation.as_ref(),
+        &command.renamed_from,
+    )
+}
+
+fn page_name(info: &AppInfo, command: &str) -> String {
+    format!("{}-{}", info.name, command)
+}
+
+/// Man page for the program itself, listing commands, global options and error codes
+pub fn render_main_page(commands: &[Termenu], info: &AppInfo) -> String {
+    let mut out = title_header(&info.name, info);
+
+    out.push_str(".SH NAME\n");
+    if info.description.trim().is_empty() {
+        out.push_str(&format!("{}\n", roff(&info.name)));
+    } else {
+        out.push_str(&format!(
+            "{} \\- {}\n",
+            roff(&info.name),
+            roff(&info.description)
+        ));
+    }
+
+    out.push_str(".SH SYNOPSIS\n");
+    out.push_str(&format!(
+        ".B {}\n[\\fIglobal options\\fR] \\fIcommand\\fR [\\fIoptions\\fR]\n",
+        roff(&info.name)
+    ));
+
+    if !info.description.trim().is_empty() {
+        out.push_str(&format!(".SH DESCRIPTION\n{}\n", roff(&info.description)));
+    }
+
+    out.push_str(".SH GLOBAL OPTIONS\nGlobal options go before the command name.\n");
+    for (name, description) in GLOBAL_OPTIONS {
+        let label = if GLOBAL_FLAGS.contains(name) {
+            format!("\\fB{}\\fR", roff(name))
+        } else {
+            format!("\\fB{}\\fR=\\fIvalue\\fR", roff(name))
+        };
+        out.push_str(&option_entry(&label, description, None));
+    }
+
+    out.push_str(".SH COMMANDS\n");
+    for cmd in commands.iter().filter(|cmd| !cmd.hidden_from_docs) {
+        out.push_str(&format!(
+            ".TP\n\\fB{}\\fR\n{}\nSee \\fB{}\\fR(1).\n",
+            roff(&cmd.command),
+            roff(&command_details(cmd)),
+            roff(&page_name(info, &cmd.command))
+        ));
+    }
+
+    out.push_str(&format!(
+        ".SH ERRORS\nErrors are reported as \\fB[code] Name: issue\\fR.\nRun \\fB{} explain\\fR \\fIcode\\fR for common causes and remedies.\n",
+        roff(&info.name)
+    ));
+    for kind in ErrorKind::all() {
+        out.push_str(&format!(
+            ".TP\n\\fB{}\\fR {}\n{}\n",
+            kind.code(),
+            kind.name(),
+            roff(kind.message())
+        ));
+    }
+
+    if let Some(authors) = info.authors_display() {
+        out.push_str(&format!(".SH AUTHORS\n{}\n", roff(&authors)));
+    }
+
+    out.push_str(".SH SEE ALSO\n");
+    let see_also: Vec<String> = commands
+        .iter()
+        .filter(|cmd| !cmd.hidden_from_docs)
+        .map(|cmd| format!("\\fB{}\\fR(1)", roff(&page_name(info, &cmd.command))))
+        .collect();
+    out.push_str(&format!("{}\n", see_also.join(",\n")));
+
+    out
+}
+
+/// Man page for a single command, e.g. `termenu-inspire(1)`
+pub fn render_command_page(command: &Termenu, info: &AppInfo) -> String {
+    let page = page_name(info, &command.command);
+    let mut out = title_header(&page, info);
+
+    out.push_str(&format!(
+        ".SH NAME\n{} \\- {}\n",
+        roff(&page),
+        roff(&command.description)
+    ));
+
+    out.push_str(&format!(
+        ".SH SYNOPSIS\n.B {} {}\n",
+        roff(&info.name),
+        roff(&command.command)
+    ));
+    let mut synopsis: Vec<String> = command
+        .options
+        .iter()
+        .map(|opt| format!("[{}]", option_label(opt)))
+        .collect();
+    synopsis.extend(
+        command
+            .arguments
+            .iter()
+            .map(|arg| format!("[\\fI{}\\fR]", roff(&arg.name))),
+    );
+    if !synopsis.is_empty() {
+        out.push_str(&format!("{}\n", synopsis.join(" ")));
+    }
+
+    out.push_str(&format!(
+        ".SH DESCRIPTION\n{}\n",
+        roff(&command_details(command))
+    ));
+    if let Some(text) = &command.long_description {
+        for paragraph in text.split("\n\n") {
+            out.push_str(&format!(".PP\n{}\n", roff(paragraph.trim())));
+        }
+    }
+
+    if !command.arguments.is_empty() {
+        out.push_str(".SH ARGUMENTS\n");
+        for arg in &command.arguments {
+            out.push_str(&option_entry(
+                &format!("\\fI{}\\fR", roff(&arg.name)),
+                &arg.details(),
+                arg.default.as_deref(),
+            ));
+        }
+    }
+
+    if !command.options.is_empty() {
+        out.push_str(".SH OPTIONS\n");
+        for opt in &command.options {
+            out.push_str(&option_entry(
+                &option_label(opt),
+                &opt.details(),
+                opt.default.as_deref(),
+            ));
+        }
+    }
+
+    if !command.examples.is_empty() {
+        out.push_str(".SH EXAMPLES\n");
+        for example in &command.examples {
+            out.push_str(&format!(
+                ".TP\n\\fB{} {}\\fR\n{}\n",
+                roff(&info.name),
+                roff(&example.command),
+                roff(&example.description)
+            ));
+        }
+    }
+
+    out.push_str(&format!(
+        ".SH ERRORS\nSee \\fB{}\\fR(1) for the error code catalog.\n",
+        roff(&info.name)
+    ));
+    let mut see_also = vec![format!("\\fB{}\\fR(1)", roff(&info.name))];
+    see_also.extend(
+        command
+            .see_also
+            .iter()
+            .map(|name| format!("\\fB{}\\fR(1)", roff(&page_name(info, name)))),
+    );
+    out.push_str(&format!(".SH SEE ALSO\n{}\n", see_also.join(",\n")));
+
+    out
+}
+
+/// Write `<name>.1` and one `<name>-<command>.1` per command into `dir`
+///
+/// Intended for build scripts and packaging as well as the `man` built-in.
+pub fn write_man_pages(
+    commands: &[Termenu],
+    info: &AppInfo,
+    dir: &Path,
+) -> Result<Vec<PathBuf>, TermenuError> {
+    let io_error = |path: &Path, e: std::io::Error| {
+        TermenuError::framework_resource_error(Some(json!({
+            "issue": format!("Failed to write {}: {}", path.display(), e)
+        })))
+    };
+
+    fs::create_dir_all(dir).map_err(|e| io_error(dir, e))?;
+
+    let mut written = Vec::new();
+    let main_path = dir.join(format!("{}.1", info.name));
+    fs::write(&main_path, render_main_page(commands, info)).map_err(|e| io_error(&main_path, e))?;
+    written.push(main_path);
+
+    for cmd in commands.iter().filter(|cmd| !cmd.hidden_from_docs) {
+        let path = dir.join(format!("{}.1", page_name(info, &cmd.command)));
+        fs::write(&path, render_command_page(cmd, info)).map_err(|e| io_error(&path, e))?;
+        written.push(path);
+    }
+
+    Ok(written)
+}
+
+/// Built-in `man` command
+pub fn register() -> Termenu {
+    let mut command = Termenu::new_command_with_context(
+        "man",
+        "Generate roff man pages for the program and its commands.",
+        |ctx: &Context| {
+            let registry = ctx.state::<Registry>()?;
+            let info = AppInfo::current();
+
+            if let Some(dir) = ctx.option("--output") {
+                let written = write_man_pages(registry.commands(), &info, Path::new(dir))?;
+                println!(
+                    "{} Wrote {} man pages to {}",
+                    "✔".green(),
+                    written.len(),
+                    dir
+                );
+                return Ok(());
+            }
+
+            match ctx.option("--command") {
+                Some(name) => {
+                    let cmd = registry.find(name).ok_or_else(|| {
+                        TermenuError::invalid_command_error(Some(json!({
+                            "issue": format!("invalid command '{}'", name)
+                        })))
+                    })?;
+                    print!("{}", render_command_page(cmd, &info));
+                }
+                None => print!("{}", render_main_page(registry.commands(), &info)),
+            }
+            Ok(())
+        },
+    );
+
+    command
+        .add_option(
+            "--command",
+            "Print the page of a single command instead of the main page.",
+        )
+        .value();
+    command
+        .add_option(
+            "--output",
+            "Write all pages into this directory instead of printing.",
+        )
+        .value();
+
+    command
+        .add_example(
+            "man | man -l -",
+            "Read the main page without installing it.",
+        )
+        .add_example(
+            "man --output=target/man",
+            "Write every page, e.g. when packaging a release.",
+        )
+        .see_also(&["docs", "completions"])
+        .error_codes(&["E013"]);
+
+    command
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_command_page_escapes_option_names() {
+        let mut command = Termenu::new_command("demo", "A demo command.", |_| Ok(()));
+        command
+            .add_option("--format", "Output format.")
+            .choices(&["text", "json"]);
+        let info = AppInfo::new("app", "1.0.0", "", "");
+
+        let page = render_command_page(&command, &info);
+        assert!(page.starts_with(".TH \"APP-DEMO\" \"1\""));
+        assert!(page.contains(".SH OPTIONS\n.TP\n\\fB\\-\\-format\\fR=\\fItext|json\\fR"));
+        assert!(page.contains("Possible values: text, json"));
+    }
+}
@@ -1,7 +1,41 @@
+pub mod app_info;
 pub mod banner;
+pub mod completions;
+pub mod context;
 pub mod core;
+pub mod crash;
+pub mod deprecation;
+pub mod docs;
 pub mod exceptions;
+pub mod explain;
+pub mod globals;
+pub mod jobs;
+pub mod layout;
+pub mod manpage;
+pub mod option;
+pub mod report;
+pub mod schedule;
+pub mod schema;
+pub mod state;
+pub mod version;
+pub mod watch;
 
+pub use app_info::*;
 pub use banner::*;
+pub use context::*;
 pub use core::*;
+pub use crash::CRASH_EXIT_CODE;
+pub use deprecation::Deprecation;
+pub use docs::{DocsFormat, render as render_docs};
 pub use exceptions::*;
+pub use explain::Explanation;
+pub use globals::*;
+pub use jobs::{JobRecord, JobStatus};
+pub use manpage::{render_command_page, render_main_page, write_man_pages};
+pub use option::*;
+pub use report::ErrorFormat;
+pub use schedule::*;
+pub use schema::{SCHEMA_VERSION, Schema};
+pub use state::*;
+pub use version::BuildInfo;
+pub use watch::*;
@@ -0,0 +1,248 @@
+use serde_json::json;
+use std::fmt;
+use std::sync::Arc;
+
+use super::deprecation::{self, Deprecation};
+use super::exceptions::TermenuError;
+use super::report;
+
+/// A completion suggestion with an optional description
+#[derive(Debug, Clone, PartialEq)]
+pub struct Candidate {
+    pub value: String,
+    pub description: Option<String>,
+}
+
+impl Candidate {
+    pub fn new(value: &str) -> Self {
+        Self {
+            value: value.to_string(),
+            description: None,
+        }
+    }
+
+    pub fn with_description(value: &str, description: &str) -> Self {
+        Self {
+            value: value.to_string(),
+            description: Some(description.to_string()),
+        }
+    }
+}
+
+/// Callback producing runtime completion values for the partial word typed so far
+pub type CompletionProvider = Arc<dyn Fn(&str) -> Vec<Candidate> + Send + Sync>;
+
+/// What kind of value an option or argument accepts
+#[derive(Debug, Clone, PartialEq)]
+pub enum OptionKind {
+    /// Either `--name` or `--name=value` (the original untyped behaviour)
+    Any,
+    /// Only `--name`, no value
+    Flag,
+    /// Only `--name=<value>`
+    Value,
+    /// `--name=<value>` where the value must be one of a fixed set
+    Choice(Vec<String>),
+}
+
+/// An option (`--name`) or positional argument registered on a command
+#[derive(Clone)]
+pub struct CommandOption {
+    pub name: String,
+    pub description: String,
+    pub kind: OptionKind,
+    pub completer: Option<CompletionProvider>,
+    /// Value used when the option is omitted
+    pub default: Option<String>,
+    /// Parsing fails when the option is omitted
+    pub required: bool,
+    /// Set when the option itself is on its way out
+    pub deprecation: Option<Deprecation>,
+    /// Old names still accepted for this option
+    pub renamed_from: Vec<(String, Deprecation)>,
+}
+
+impl fmt::Debug for CommandOption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CommandOption")
+            .field("name", &self.name)
+            .field("description", &self.description)
+            .field("kind", &self.kind)
+            .field("completer", &self.completer.is_some())
+            .field("default", &self.default)
+            .field("required", &self.required)
+            .field("deprecation", &self.deprecation)
+            .field("renamed_from", &self.renamed_from)
+            .finish()
+    }
+}
+
+impl CommandOption {
+    pub fn new(name: &str, description: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            description: description.to_string(),
+            kind: OptionKind::Any,
+            completer: None,
+            default: None,
+            required: false,
+            deprecation: None,
+            renamed_from: Vec::new(),
+        }
+    }
+
+    /// Suggest values at runtime, e.g. existing job ids or profile names
+    pub fn complete_with<F>(&mut self, provider: F) -> &mut Self
+    where
+        F: Fn(&str) -> Vec<Candidate> + Send + Sync + 'static,
+    {
+        self.completer = Some(Arc::new(provider));
+        self
+    }
+
+    /// Completion candidates for a partially typed value
+    pub fn complete(&self, partial: &str) -> Vec<Candidate> {
+        let mut candidates: Vec<Candidate> = self
+            .possible_values()
+            .iter()
+            .map(|v| Candidate::new(v))
+            .collect();
+        if let Some(provider) = &self.completer {
+            candidates.extend(provider(partial));
+        }
+        candidates.retain(|c| c.value.starts_with(partial));
+        candidates
+    }
+
+    /// Accept only `--name`
+    pub fn flag(&mut self) -> &mut Self {
+        self.kind = OptionKind::Flag;
+        self
+    }
+
+    /// Require a value (`--name=<value>`)
+    pub fn value(&mut self) -> &mut Self {
+        self.kind = OptionKind::Value;
+        self
+    }
+
+    /// Require a value from a fixed set
+    pub fn choices(&mut self, values: &[&str]) -> &mut Self {
+        self.kind = OptionKind::Choice(values.iter().map(|v| v.to_string()).collect());
+        self
+    }
+
+    /// Fill in `value` when the option is not given
+    pub fn default_value(&mut self, value: &str) -> &mut Self {
+        self.default = Some(value.to_string());
+        self
+    }
+
+    /// Fail parsing when the option is not given
+    pub fn required(&mut self) -> &mut Self {
+        self.required = true;
+        self
+    }
+
+    /// Keep accepting the option with a warning, and leave it out of default help
+    pub fn deprecated(&mut self, replacement: Option<&str>, removal: Option<&str>) -> &mut Self {
+        self.deprecation = Some(Deprecation::new(replacement, removal));
+        self
+    }
+
+    /// Accept `old` as a deprecated name of this option
+    pub fn renamed_from(&mut self, old: &str, removal: Option<&str>) -> &mut Self {
+        let deprecation = Deprecation::new(Some(&self.name), removal);
+        self.renamed_from.push((old.to_string(), deprecation));
+        self
+    }
+
+    /// Whether `name` is this option, under its current or a deprecated name
+    pub fn is_named(&self, name: &str) -> bool {
+        self.name == name || self.renamed_from.iter().any(|(old, _)| old == name)
+    }
+
+    /// Whether the option shows up in default help and completions
+    pub fn is_listed(&self) -> bool {
+        self.deprecation.is_none()
+    }
+
+    /// Print the deprecation warnings that apply when the option is given as `name`
+    pub fn warn_if_deprecated(&self, name: &str) {
+        if let Some((_, deprecation)) = self.renamed_from.iter().find(|(old, _)| old == name) {
+            deprecation.warn("Option", name);
+        }
+        if let Some(deprecation) = &self.deprecation {
+            deprecation.warn("Option", &self.name);
+        }
+    }
+
+    /// Whether the option can be given a value
+    pub fn takes_value(&self) -> bool {
+        !matches!(self.kind, OptionKind::Flag)
+    }
+
+    /// Usage label, e.g. `--format=<text|json>`, with `name` and `value` styling the parts
+    /// for the output format
+    pub fn label(&self, name: impl Fn(&str) -> String, value: impl Fn(&str) -> String) -> String {
+        let name = name(&self.name);
+        match &self.kind {
+            OptionKind::Flag => name,
+            OptionKind::Any => format!("{}[={}]", name, value("value")),
+            OptionKind::Value => format!("{}={}", name, value("value")),
+            OptionKind::Choice(values) => format!("{}={}", name, value(&values.join("|"))),
+        }
+    }
+
+    /// Description followed by the requirement, the accepted values and deprecations
+    pub fn details(&self) -> String {
+        let mut details = self.description.clone();
+        if self.required {
+            details.push_str(" Required.");
+        }
+        if let values @ [_, ..] = self.possible_values() {
+            details.push_str(&format!(" Possible values: {}.", values.join(", ")));
+        }
+        deprecation::annotate(&details, self.deprecation.as_ref(), &self.renamed_from)
+    }
+
+    /// Fixed values accepted by this option, if any
+    pub fn possible_values(&self) -> &[String] {
+        match &self.kind {
+            OptionKind::Choice(values) => values,
+            _ => &[],
+        }
+    }
+
+    /// Check a parsed value against the option kind
+    pub fn check(&self, value: Option<&str>) -> Result<(), TermenuError> {
+        match (&self.kind, value) {
+            (OptionKind::Flag, Some(_)) => Err(TermenuError::invalid_command_error(Some(json!({
+                "issue": format!("Option '{}' does not take a value", self.name)
+            })))
+            .with_hint(&format!("Drop the value and pass just '{}'.", self.name))),
+            (OptionKind::Value | OptionKind::Choice(_), None) => {
+                Err(TermenuError::input_missing_error(Some(json!({
+                    "issue": format!("Option '{}' requires a value ({}=<value>)", self.name, self.name)
+                }))))
+            }
+            (OptionKind::Choice(values), Some(value)) if !values.iter().any(|v| v == value) => {
+                let error = TermenuError::input_unknown_error(Some(json!({
+                    "issue": format!(
+                        "Invalid value '{}' for '{}'. Possible values: {}",
+                        value,
+                        self.name,
+                        values.join(", ")
+                    )
+                })));
+                Err(
+                    match report::closest(value, values.iter().map(String::as_str)) {
+                        Some(close) => error.with_hint(&format!("Did you mean '{}'?", close)),
+                        None => error,
+                    },
+                )
+            }
+            _ => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,267 @@
+use colored::{ColoredString, Colorize};
+use serde_json::{Value, json};
+use std::env;
+use std::error::Error;
+use std::io::{self, IsTerminal};
+use std::sync::RwLock;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use super::app_info::AppInfo;
+use super::exceptions::TermenuError;
+
+static SELECTED: RwLock<Option<ErrorFormat>> = RwLock::new(None);
+static JSON_WHEN_PIPED: AtomicBool = AtomicBool::new(false);
+
+/// How errors are written to stderr
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorFormat {
+    /// `[Error] [code] Name: issue`, colored on terminals
+    Text,
+    /// One JSON object per error on a single line
+    Json,
+}
+
+impl ErrorFormat {
+    pub fn parse(value: &str) -> Result<Self, TermenuError> {
+        match value {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            other => Err(TermenuError::input_unknown_error(Some(json!({
+                "issue": format!("Invalid --output value: '{}' (expected text or json)", other)
+            })))),
+        }
+    }
+
+    /// Use `format` for this run, e.g. from the `--output` global option
+    pub fn select(format: ErrorFormat) {
+        *SELECTED.write().unwrap_or_else(|e| e.into_inner()) = Some(format);
+    }
+
+    /// Opt in to JSON errors whenever stderr is not a terminal
+    ///
+    /// An explicit `--output` still wins.
+    pub fn json_when_piped(enabled: bool) {
+        JSON_WHEN_PIPED.store(enabled, Ordering::Relaxed);
+    }
+
+    /// The selected format, else JSON for piped stderr if opted in, else text
+    pub fn current() -> ErrorFormat {
+        if let Some(format) = *SELECTED.read().unwrap_or_else(|e| e.into_inner()) {
+            return format;
+        }
+        if JSON_WHEN_PIPED.load(Ordering::Relaxed) && !io::stderr().is_terminal() {
+            ErrorFormat::Json
+        } else {
+            ErrorFormat::Text
+        }
+    }
+}
+
+impl TermenuError {
+    /// Machine-readable form: code, name, category, message, the full `data`, the hint, docs
+    /// link and argument index, and the source chain
+    pub fn to_json(&self) -> Value {
+        let kind = self.kind();
+        let data = match self {
+            TermenuError::Exception { data, .. } => data.clone().unwrap_or(Value::Null),
+            TermenuError::Io(_) => Value::Null,
+        };
+
+        let mut sources = Vec::new();
+        let mut source = self.source();
+        while let Some(error) = source {
+            sources.push(error.to_string());
+            source = error.source();
+        }
+
+        json!({
+            "code": kind.code(),
+            "name": kind.name(),
+            "category": kind.category().label(),
+            "message": self.message(),
+            "data": data,
+            "hint": self.hint(),
+            "docs": self.docs(),
+            "argument": self.argument(),
+            "sources": sources,
+        })
+    }
+
+    /// Write the error to stderr in the current `ErrorFormat`, styled only on terminals
+    pub fn report(&self) {
+        match ErrorFormat::current() {
+            ErrorFormat::Text => {
+                let args: Vec<String> = env::args().collect();
+                eprintln!("{}", self.render(&args, io::stderr().is_terminal()));
+            }
+            ErrorFormat::Json => eprintln!("{}", json!({ "error": self.to_json() })),
+        }
+    }
+
+    /// `[code] Name: issue` in color, for terminals; `Display` gives the plain form
+    pub fn styled(&self) -> String {
+        match self {
+            TermenuError::Exception { kind, .. } => format!(
+                "[{}] {}: {}",
+                kind.code().red().bold(),
+                kind.name().yellow().bold(),
+                self.message().cyan()
+            ),
+            TermenuError::Io(err) => err.to_string().red().to_string(),
+        }
+    }
+
+    /// Text form with the command line, a caret under the offending argument, the hint and docs link
+    ///
+    /// `args` is the full command line, program name first, as from `env::args()`. Without
+    /// `styled` the result has no color codes.
+    pub fn render(&self, args: &[String], styled: bool) -> String {
+        let paint = |text: ColoredString| {
+            if styled {
+                text.to_string()
+            } else {
+                text.clear().to_string()
+            }
+        };
+        let error = if styled {
+            self.styled()
+        } else {
+            self.to_string()
+        };
+        let mut out = format!("[{}] {}", paint("Error".red().bold()), error);
+
+        if let Some(index) = self.argument().filter(|&index| index < args.len()) {
+            let mut line = String::new();
+            let mut caret = String::new();
+            for (i, arg) in args.iter().enumerate() {
+                let shown = if i == 0 {
+                    AppInfo::current().name
+                } else if arg.contains(char::is_whitespace) {
+                    format!("'{}'", arg)
+                } else {
+                    arg.clone()
+                };
+                if i > 0 {
+                    line.push(' ');
+                }
+                if i == index {
+                    caret = format!(
+                        "{}{}",
+                        " ".repeat(line.chars().count()),
+                        "^".repeat(shown.chars().count().max(1))
+                    );
+                }
+                line.push_str(&shown);
+            }
+            out.push_str(&format!(
+                "\n\n    {}\n    {}",
+                line,
+                paint(caret.red().bold())
+            ));
+        }
+
+        if self.hint().is_some() || self.docs().is_some() {
+            out.push('\n');
+        }
+        if let Some(hint) = self.hint() {
+            out.push_str(&format!("\n  {} {}", paint("hint:".green().bold()), hint));
+        }
+        if let Some(docs) = self.docs() {
+            out.push_str(&format!("\n  {} {}", paint("docs:".blue().bold()), docs));
+        }
+        out
+    }
+}
+
+/// `text` for stderr: styled on terminals, plain otherwise
+///
+/// `colored` decides by stdout, so `cmd 2>run.log` would otherwise log color codes.
+pub fn on_stderr(text: ColoredString) -> String {
+    if io::stderr().is_terminal() {
+        text.to_string()
+    } else {
+        text.clear().to_string()
+    }
+}
+
+/// The candidate closest to a mistyped `input`, if it is close enough to be a likely typo
+pub fn closest<'a>(input: &str, candidates: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+    let limit = (input.chars().count() / 3).max(1);
+    candidates
+        .into_iter()
+        .map(|candidate| (edit_distance(input, candidate), candidate))
+        .filter(|(distance, _)| *distance <= limit)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// Levenshtein distance between two strings
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_keeps_full_data_and_sources() {
+        let error = TermenuError::invalid_command_error(Some(json!({
+            "issue": "Unknown option: '--x'",
+            "option": "--x"
+        })));
+        let value = error.to_json();
+        assert_eq!(value["code"], "E712");
+        assert_eq!(value["name"], "InvalidCommandError");
+        assert_eq!(value["message"], "Unknown option: '--x'");
+        assert_eq!(value["data"]["option"], "--x");
+        assert_eq!(value["sources"], json!([]));
+
+        // Diagnostics get their own keys and leave the app's data alone
+        let error = TermenuError::input_unknown_error(Some(json!(["a", "b"])))
+            .with_hint("Try again.")
+            .at_argument(1)
+            .context("reading input");
+        let value = error.to_json();
+        assert_eq!(value["hint"], "Try again.");
+        assert_eq!(value["argument"], 1);
+        assert_eq!(value["docs"], Value::Null);
+        assert_eq!(value["data"]["value"], json!(["a", "b"]));
+        assert_eq!(value["message"], "reading input: The input is not valid.");
+
+        let io = TermenuError::Io(io::Error::new(io::ErrorKind::NotFound, "gone"));
+        assert_eq!(io.to_json()["sources"], json!(["gone"]));
+    }
+
+    #[test]
+    fn test_render_points_at_argument_and_shows_hint() {
+        // The program is shown by its app name, here the package's
+        let args: Vec<String> = ["target/debug/termenu", "hello", "--nmae=x"]
+            .iter()
+            .map(|a| a.to_string())
+            .collect();
+        let error = TermenuError::invalid_command_error(Some(json!({
+            "issue": "Unknown option: '--nmae'"
+        })))
+        .at_argument(2)
+        .with_hint("Did you mean '--name'?");
+
+        assert_eq!(
+            error.render(&args, false),
+            "[Error] [E712] InvalidCommandError: Unknown option: '--nmae'\n\n    \
+             termenu hello --nmae=x\n                  ^^^^^^^^\n\n  hint: Did you mean '--name'?"
+        );
+        assert_eq!(closest("--nmae", ["--name", "--verbose"]), Some("--name"));
+        assert_eq!(closest("--zzz", ["--name"]), None);
+    }
+}
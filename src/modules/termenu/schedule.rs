@@ -0,0 +1,407 @@
+use chrono::{
+    DateTime, Datelike, Duration as ChronoDuration, Local, NaiveDateTime, TimeZone, Timelike,
+};
+use colored::Colorize;
+use serde_json::json;
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+use super::exceptions::TermenuError;
+use super::report::{ErrorFormat, on_stderr};
+use super::watch::format_duration;
+
+/// How often a scheduled command runs
+#[derive(Debug, Clone)]
+pub enum Schedule {
+    /// Fixed interval, first run happens immediately
+    Every(Duration),
+    /// Standard five-field cron expression, evaluated in local time
+    Cron(CronExpr),
+}
+
+/// Settings for `--every` / `--cron`
+#[derive(Debug, Clone)]
+pub struct ScheduleConfig {
+    pub schedule: Schedule,
+    /// Stop after this many consecutive failed runs
+    pub max_failures: Option<u32>,
+}
+
+impl Schedule {
+    /// The next time the command should run, strictly after `now`
+    pub fn next_after(&self, now: DateTime<Local>) -> Option<DateTime<Local>> {
+        match self {
+            Schedule::Every(interval) => ChronoDuration::from_std(*interval)
+                .ok()
+                .and_then(|interval| now.checked_add_signed(interval)),
+            Schedule::Cron(expr) => expr.next_after(now),
+        }
+    }
+}
+
+/// Parse an `--every` interval such as `30s`, `5m`, `2h`, `1d` or `1h30m`
+///
+/// Intervals too long to schedule a next run from now are rejected.
+pub fn parse_interval(value: &str) -> Result<Duration, TermenuError> {
+    let invalid = || {
+        TermenuError::input_unknown_error(Some(json!({
+            "issue": format!("Invalid interval: '{}' (expected e.g. 30s, 5m, 2h, 1d)", value)
+        })))
+    };
+
+    let mut total: u64 = 0;
+    let mut digits = String::new();
+    for ch in value.trim().chars() {
+        if ch.is_ascii_digit() {
+            digits.push(ch);
+            continue;
+        }
+        let amount: u64 = digits.parse().map_err(|_| invalid())?;
+        let unit = match ch {
+            's' => 1,
+            'm' => 60,
+            'h' => 60 * 60,
+            'd' => 24 * 60 * 60,
+            _ => return Err(invalid()),
+        };
+        total = amount
+            .checked_mul(unit)
+            .and_then(|secs| total.checked_add(secs))
+            .ok_or_else(invalid)?;
+        digits.clear();
+    }
+    if !digits.is_empty() {
+        // A bare number is read as seconds
+        let secs = digits.parse::<u64>().map_err(|_| invalid())?;
+        total = total.checked_add(secs).ok_or_else(invalid)?;
+    }
+
+    if total == 0 {
+        return Err(invalid());
+    }
+    let interval = Duration::from_secs(total);
+    let schedulable = ChronoDuration::from_std(interval)
+        .ok()
+        .and_then(|interval| Local::now().checked_add_signed(interval))
+        .is_some();
+    if !schedulable {
+        return Err(TermenuError::input_unknown_error(Some(json!({
+            "issue": format!("--every interval is too long: '{}'", value)
+        })))
+        .with_hint("Use a shorter interval, e.g. 365d."));
+    }
+    Ok(interval)
+}
+
+/// A parsed `minute hour day-of-month month day-of-week` expression
+#[derive(Debug, Clone, PartialEq)]
+pub struct CronExpr {
+    minutes: Vec<bool>,
+    hours: Vec<bool>,
+    days_of_month: Vec<bool>,
+    months: Vec<bool>,
+    days_of_week: Vec<bool>,
+    dom_restricted: bool,
+    dow_restricted: bool,
+}
+
+impl CronExpr {
+    pub fn parse(expr: &str) -> Result<Self, TermenuError> {
+        let fields: Vec<&str> = expr.split_whitespace().collect();
+        if fields.len() != 5 {
+            return Err(TermenuError::input_unknown_error(Some(json!({
+                "issue": format!(
+                    "Invalid cron expression: '{}' (expected 5 fields: minute hour day month weekday)",
+                    expr
+                )
+            }))));
+        }
+
+        let mut days_of_week = parse_cron_field(fields[4], 0, 7, expr)?;
+        // Both 0 and 7 mean Sunday
+        if days_of_week[7] {
+            days_of_week[0] = true;
+        }
+        days_of_week.truncate(7);
+
+        Ok(Self {
+            minutes: parse_cron_field(fields[0], 0, 59, expr)?,
+            hours: parse_cron_field(fields[1], 0, 23, expr)?,
+            days_of_month: parse_cron_field(fields[2], 1, 31, expr)?,
+            months: parse_cron_field(fields[3], 1, 12, expr)?,
+            days_of_week,
+            dom_restricted: !fields[2].starts_with('*'),
+            dow_restricted: !fields[4].starts_with('*'),
+        })
+    }
+
+    fn matches_day(&self, date: &NaiveDateTime) -> bool {
+        let dom = self.days_of_month[date.day() as usize];
+        let dow = self.days_of_week[date.weekday().num_days_from_sunday() as usize];
+        // Classic cron: when both day fields are restricted, either one may match
+        match (self.dom_restricted, self.dow_restricted) {
+            (true, true) => dom || dow,
+            (true, false) => dom,
+            (false, true) => dow,
+            (false, false) => true,
+        }
+    }
+
+    /// The next matching minute strictly after `now`
+    pub fn next_after(&self, now: DateTime<Local>) -> Option<DateTime<Local>> {
+        let mut candidate =
+            now.naive_local().with_second(0)?.with_nanosecond(0)? + ChronoDuration::minutes(1);
+        // Give up after five years without a match (e.g. `0 0 30 2 *`)
+        let limit = candidate + ChronoDuration::days(5 * 366);
+
+        while candidate < limit {
+            if !self.months[candidate.month() as usize] {
+                let (year, month) = if candidate.month() == 12 {
+                    (candidate.year() + 1, 1)
+                } else {
+                    (candidate.year(), candidate.month() + 1)
+                };
+                candidate =
+                    chrono::NaiveDate::from_ymd_opt(year, month, 1)?.and_hms_opt(0, 0, 0)?;
+                continue;
+            }
+            if !self.matches_day(&candidate) {
+                candidate = candidate.date().succ_opt()?.and_hms_opt(0, 0, 0)?;
+                continue;
+            }
+            if !self.hours[candidate.hour() as usize] {
+                candidate = candidate.with_minute(0)? + ChronoDuration::hours(1);
+                continue;
+            }
+            if !self.minutes[candidate.minute() as usize] {
+                candidate += ChronoDuration::minutes(1);
+                continue;
+            }
+
+            // Skip local times that do not exist because of a DST jump
+            if let Some(resolved) = Local.from_local_datetime(&candidate).earliest() {
+                return Some(resolved);
+            }
+            candidate += ChronoDuration::minutes(1);
+        }
+
+        None
+    }
+}
+
+/// Parse one cron field into a lookup table indexed by value
+fn parse_cron_field(
+    field: &str,
+    min: u32,
+    max: u32,
+    expr: &str,
+) -> Result<Vec<bool>, TermenuError> {
+    let invalid = || {
+        TermenuError::input_unknown_error(Some(json!({
+            "issue": format!("Invalid cron field '{}' in '{}' (allowed {}-{})", field, expr, min, max)
+        })))
+    };
+
+    let mut table = vec![false; max as usize + 1];
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (range, step.parse::<u32>().map_err(|_| invalid())?),
+            None => (part, 1),
+        };
+        if step == 0 {
+            return Err(invalid());
+        }
+
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((start, end)) = range.split_once('-') {
+            (
+                start.parse::<u32>().map_err(|_| invalid())?,
+                end.parse::<u32>().map_err(|_| invalid())?,
+            )
+        } else {
+            let value = range.parse::<u32>().map_err(|_| invalid())?;
+            // `5/15` means "from 5 to the end, every 15"
+            if part.contains('/') {
+                (value, max)
+            } else {
+                (value, value)
+            }
+        };
+
+        if start < min || end > max || start > end {
+            return Err(invalid());
+        }
+        for value in (start..=end).step_by(step as usize) {
+            table[value as usize] = true;
+        }
+    }
+
+    Ok(table)
+}
+
+/// Resolves when the process is asked to stop (Ctrl-C, or SIGTERM on Unix)
+pub async fn shutdown_signal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{SignalKind, signal};
+        match signal(SignalKind::terminate()) {
+            Ok(mut term) => {
+                tokio::select! {
+                    _ = tokio::signal::ctrl_c() => {}
+                    _ = term.recv() => {}
+                }
+            }
+            Err(_) => {
+                let _ = tokio::signal::ctrl_c().await;
+            }
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = tokio::signal::ctrl_c().await;
+    }
+}
+
+/// Run `run` on the given schedule until a shutdown signal or too many failures
+pub async fn run_scheduled<F, Fut>(config: &ScheduleConfig, mut run: F) -> Result<(), TermenuError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<(), TermenuError>>,
+{
+    let mut run_count: u64 = 0;
+    let mut consecutive_failures: u32 = 0;
+    let mut next = match config.schedule {
+        Schedule::Every(_) => Some(Local::now()),
+        Schedule::Cron(ref expr) => expr.next_after(Local::now()),
+    };
+
+    loop {
+        let Some(at) = next else {
+            return Err(TermenuError::input_unknown_error(Some(json!({
+                "issue": "Schedule never fires; check the cron expression."
+            }))));
+        };
+
+        let wait = (at - Local::now()).to_std().unwrap_or_default();
+        if !wait.is_zero() {
+            println!(
+                "{} Next run at {}",
+                "⏱".cyan(),
+                at.format("%Y-%m-%d %H:%M:%S").to_string().bold()
+            );
+        }
+        tokio::select! {
+            _ = shutdown_signal() => break,
+            _ = tokio::time::sleep(wait) => {}
+        }
+
+        run_count += 1;
+        let started = Instant::now();
+        let result = tokio::select! {
+            _ = shutdown_signal() => break,
+            result = run() => result,
+        };
+        let stamp = Local::now().format("%Y-%m-%d %H:%M:%S");
+
+        match result {
+            Ok(()) => {
+                consecutive_failures = 0;
+                println!(
+                    "[{}] {} Run #{} completed in {}",
+                    stamp,
+                    "✔".green(),
+                    run_count,
+                    format_duration(started.elapsed()).bold()
+                );
+            }
+            Err(e) => {
+                consecutive_failures += 1;
+                if ErrorFormat::current() == ErrorFormat::Json {
+                    e.report();
+                } else {
+                    eprintln!(
+                        "[{}] {} Run #{} failed in {}: {}",
+                        stamp,
+                        on_stderr("✖".red()),
+                        run_count,
+                        on_stderr(format_duration(started.elapsed()).bold()),
+                        e
+                    );
+                }
+                if let Some(max) = config.max_failures
+                    && consecutive_failures >= max
+                {
+                    return Err(TermenuError::command_unknown_error(Some(json!({
+                        "issue": format!(
+                            "Stopped after {} consecutive failed runs. Last error: {}",
+                            consecutive_failures, e
+                        )
+                    }))));
+                }
+            }
+        }
+
+        next = config.schedule.next_after(Local::now());
+    }
+
+    println!(
+        "\n{} Scheduler stopped after {} runs.",
+        "■".yellow(),
+        run_count
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_interval() {
+        assert_eq!(parse_interval("5m").unwrap(), Duration::from_secs(300));
+        assert_eq!(parse_interval("1h30m").unwrap(), Duration::from_secs(5400));
+        assert_eq!(parse_interval("45").unwrap(), Duration::from_secs(45));
+        assert!(parse_interval("5x").is_err());
+        assert!(parse_interval("0s").is_err());
+        // Overflowing user input is rejected rather than panicking
+        assert!(parse_interval("999999999999999d").is_err());
+        assert!(parse_interval("18446744073709551615s1s").is_err());
+        // Fits in seconds, but not in a chrono duration or date
+        let too_long = parse_interval("99999999999999d").unwrap_err();
+        assert!(
+            too_long
+                .issue()
+                .unwrap()
+                .starts_with("--every interval is too long")
+        );
+    }
+
+    #[test]
+    fn test_cron_next_after() {
+        let expr = CronExpr::parse("0 9 * * *").unwrap();
+        let now = Local.with_ymd_and_hms(2026, 3, 10, 9, 0, 0).unwrap();
+        let next = expr.next_after(now).unwrap();
+        assert_eq!(
+            next.naive_local(),
+            chrono::NaiveDate::from_ymd_opt(2026, 3, 11)
+                .unwrap()
+                .and_hms_opt(9, 0, 0)
+                .unwrap()
+        );
+
+        let every_quarter = CronExpr::parse("*/15 * * * 1-5").unwrap();
+        // Saturday 2026-03-14 -> Monday 2026-03-16 00:00
+        let now = Local.with_ymd_and_hms(2026, 3, 14, 12, 7, 0).unwrap();
+        let next = every_quarter.next_after(now).unwrap();
+        assert_eq!(next.day(), 16);
+        assert_eq!((next.hour(), next.minute()), (0, 0));
+    }
+
+    #[test]
+    fn test_cron_rejects_invalid() {
+        assert!(CronExpr::parse("0 9 * *").is_err());
+        assert!(CronExpr::parse("60 * * * *").is_err());
+        assert!(CronExpr::parse("*/0 * * * *").is_err());
+    }
+}
@@ -0,0 +1,259 @@
+use serde::Serialize;
+use serde_json::json;
+
+use super::app_info::AppInfo;
+use super::core::Termenu;
+use super::deprecation::Deprecation;
+use super::exceptions::{ErrorKind, TermenuError};
+use super::globals::{GLOBAL_FLAGS, GLOBAL_OPTIONS};
+use super::option::{CommandOption, OptionKind};
+
+/// Bumped on any change that could break a consumer: removed or renamed fields, changed meaning
+///
+/// New fields may be added without a bump, so clients should ignore unknown keys.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// The whole command structure, as printed by `help --format=json`
+#[derive(Debug, Serialize)]
+pub struct Schema {
+    pub schema_version: u32,
+    pub name: String,
+    pub version: String,
+    pub description: String,
+    pub global_options: Vec<OptionSchema>,
+    pub commands: Vec<CommandSchema>,
+    pub errors: Vec<ErrorSchema>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CommandSchema {
+    pub name: String,
+    pub aliases: Vec<String>,
+    pub description: String,
+    pub long_description: Option<String>,
+    pub category: Option<String>,
+    pub hidden: bool,
+    pub deprecated: Option<Deprecation>,
+    /// Old names that still work, with a warning
+    pub renamed_from: Vec<OldName>,
+    /// Positional arguments, in order
+    pub arguments: Vec<OptionSchema>,
+    pub options: Vec<OptionSchema>,
+    pub examples: Vec<ExampleSchema>,
+    pub see_also: Vec<String>,
+    /// Codes this command may fail with
+    pub errors: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OptionSchema {
+    pub name: String,
+    pub description: String,
+    /// One of `flag`, `value`, `choice` or `any` (flag or value)
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    /// Accepted values for `choice`, empty otherwise
+    pub values: Vec<String>,
+    pub default: Option<String>,
+    pub required: bool,
+    pub deprecated: Option<Deprecation>,
+    /// Old names that still work, with a warning
+    pub renamed_from: Vec<OldName>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OldName {
+    pub name: String,
+    pub removal: Option<String>,
+}
+
+fn old_names(renamed_from: &[(String, Deprecation)]) -> Vec<OldName> {
+    renamed_from
+        .iter()
+        .map(|(name, deprecation)| OldName {
+            name: name.clone(),
+            removal: deprecation.removal.clone(),
+        })
+        .collect()
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExampleSchema {
+    /// Arguments after the program name
+    pub command: String,
+    pub description: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ErrorSchema {
+    pub code: String,
+    pub name: String,
+    pub category: String,
+    pub message: String,
+    /// Process exit code when a command fails with this error
+    pub exit_code: i32,
+}
+
+impl ErrorSchema {
+    fn from_kind(kind: ErrorKind) -> Self {
+        Self {
+            code: kind.code().to_string(),
+            name: kind.name().to_string(),
+            category: kind.category().label().to_string(),
+            message: kind.message().to_string(),
+            exit_code: kind.exit_code(),
+        }
+    }
+}
+
+impl OptionSchema {
+    fn from_option(option: &CommandOption) -> Self {
+        Self {
+            name: option.name.clone(),
+            description: option.description.clone(),
+            kind: match option.kind {
+                OptionKind::Any => "any",
+                OptionKind::Flag => "flag",
+                OptionKind::Value => "value",
+                OptionKind::Choice(_) => "choice",
+            },
+            values: option.possible_values().to_vec(),
+            default: option.default.clone(),
+            required: option.required,
+            deprecated: option.deprecation.clone(),
+            renamed_from: old_names(&option.renamed_from),
+        }
+    }
+}
+
+impl CommandSchema {
+    fn from_command(command: &Termenu) -> Self {
+        let options = || command.options.iter().chain(&command.arguments);
+
+        // Errors raised while parsing, then the ones the handler declares
+        let mut errors = vec![ErrorKind::InvalidCommand.code().to_string()];
+        let takes_value =
+            |opt: &CommandOption| matches!(opt.kind, OptionKind::Value | OptionKind::Choice(_));
+        if options().any(|opt| opt.required || takes_value(opt)) {
+            errors.push(ErrorKind::InputMissing.code().to_string());
+        }
+        if options().any(|opt| matches!(opt.kind, OptionKind::Choice(_))) {
+            errors.push(ErrorKind::Input.code().to_string());
+        }
+        for code in &command.error_codes {
+            if !errors.contains(code) {
+                errors.push(code.clone());
+            }
+        }
+
+        Self {
+            name: command.command.clone(),
+            aliases: command.aliases.clone(),
+            description: command.description.clone(),
+            long_description: command.long_description.clone(),
+            category: command.category.clone(),
+            hidden: command.hidden,
+            deprecated: command.deprecation.clone(),
+            renamed_from: old_names(&command.renamed_from),
+            arguments: command
+                .arguments
+                .iter()
+                .map(OptionSchema::from_option)
+                .collect(),
+            options: command
+                .options
+                .iter()
+                .map(OptionSchema::from_option)
+                .collect(),
+            examples: command
+                .examples
+                .iter()
+                .map(|example| ExampleSchema {
+                    command: example.command.clone(),
+                    description: example.description.clone(),
+                })
+                .collect(),
+            see_also: command.see_also.clone(),
+            errors,
+        }
+    }
+}
+
+impl Schema {
+    /// Describe every command, hidden ones included (see `hidden`)
+    pub fn new(commands: &[Termenu], info: &AppInfo) -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            name: info.name.clone(),
+            version: info.version.clone(),
+            description: info.description.clone(),
+            global_options: GLOBAL_OPTIONS
+                .iter()
+                .map(|(name, description)| OptionSchema {
+                    name: name.to_string(),
+                    description: description.to_string(),
+                    kind: if GLOBAL_FLAGS.contains(name) {
+                        "flag"
+                    } else {
+                        "value"
+                    },
+                    values: Vec::new(),
+                    default: None,
+                    required: false,
+                    deprecated: None,
+                    renamed_from: Vec::new(),
+                })
+                .collect(),
+            commands: commands.iter().map(CommandSchema::from_command).collect(),
+            errors: ErrorKind::all()
+                .into_iter()
+                .map(ErrorSchema::from_kind)
+                .collect(),
+        }
+    }
+
+    /// Print as pretty JSON
+    pub fn print_json(&self) -> Result<(), TermenuError> {
+        let output = serde_json::to_string_pretty(self).map_err(|e| {
+            TermenuError::framework_unknown_error(Some(json!({
+                "issue": format!("Failed to serialize command schema: {}", e)
+            })))
+        })?;
+        println!("{}", output);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_command_schema_lists_types_and_errors() {
+        let mut command = Termenu::new_command("fetch", "Fetch a page.", |_| Ok(()));
+        command.alias("get").error_codes(&["E611"]);
+        command
+            .add_option("--url", "Page to fetch.")
+            .value()
+            .required();
+        command
+            .add_option("--format", "Output format.")
+            .choices(&["text", "json"])
+            .default_value("text");
+        let info = AppInfo::new("app", "1.0.0", "", "");
+
+        let schema = serde_json::to_value(Schema::new(&[command], &info)).unwrap();
+        let fetch = &schema["commands"][0];
+        assert_eq!(schema["schema_version"], SCHEMA_VERSION);
+        assert_eq!(fetch["aliases"], json!(["get"]));
+        assert_eq!(fetch["options"][0]["type"], "value");
+        assert_eq!(fetch["options"][0]["required"], true);
+        assert_eq!(fetch["options"][1]["values"], json!(["text", "json"]));
+        assert_eq!(fetch["options"][1]["default"], "text");
+        assert_eq!(fetch["errors"], json!(["E712", "E112", "E111", "E611"]));
+
+        // A choice given without a value fails with E112 too
+        let version = CommandSchema::from_command(&crate::modules::termenu::version::register());
+        assert_eq!(version.errors, vec!["E712", "E112", "E111", "E011"]);
+    }
+}
@@ -0,0 +1,142 @@
+use serde_json::json;
+use std::any::{Any, TypeId, type_name};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+
+use super::exceptions::TermenuError;
+
+type Resource = Arc<dyn Any + Send + Sync>;
+type Factory = Box<dyn Fn() -> Result<Resource, TermenuError> + Send + Sync>;
+
+/// A single registered resource, built on first access
+struct Slot {
+    value: OnceLock<Resource>,
+    factory: Option<Factory>,
+    init_lock: Mutex<()>,
+}
+
+/// Shared application state, keyed by type
+///
+/// Resources are registered once when commands are set up and fetched
+/// by type from handlers via `Context::state::<T>()`.
+#[derive(Default)]
+pub struct State {
+    slots: HashMap<TypeId, Slot>,
+}
+
+impl State {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a resource that is built the first time a handler asks for it
+    pub fn register<T, F>(&mut self, factory: F)
+    where
+        T: Any + Send + Sync,
+        F: Fn() -> Result<T, TermenuError> + Send + Sync + 'static,
+    {
+        self.slots.insert(
+            TypeId::of::<T>(),
+            Slot {
+                value: OnceLock::new(),
+                factory: Some(Box::new(move || {
+                    factory().map(|value| Arc::new(value) as Resource)
+                })),
+                init_lock: Mutex::new(()),
+            },
+        );
+    }
+
+    /// Register an already built resource
+    pub fn insert<T>(&mut self, value: T)
+    where
+        T: Any + Send + Sync,
+    {
+        let cell = OnceLock::new();
+        let _ = cell.set(Arc::new(value) as Resource);
+        self.slots.insert(
+            TypeId::of::<T>(),
+            Slot {
+                value: cell,
+                factory: None,
+                init_lock: Mutex::new(()),
+            },
+        );
+    }
+
+    /// Check whether a resource of type `T` has been registered
+    pub fn contains<T: Any>(&self) -> bool {
+        self.slots.contains_key(&TypeId::of::<T>())
+    }
+
+    /// Fetch a resource by type, initializing it on first access
+    pub fn get<T>(&self) -> Result<Arc<T>, TermenuError>
+    where
+        T: Any + Send + Sync,
+    {
+        let slot = self.slots.get(&TypeId::of::<T>()).ok_or_else(|| {
+            TermenuError::framework_resource_error(Some(json!({
+                "issue": format!("State '{}' is not registered.", type_name::<T>())
+            })))
+        })?;
+
+        let resource = match slot.value.get() {
+            Some(resource) => resource.clone(),
+            None => {
+                // Only one caller runs the factory; failures are not cached
+                let _guard = slot.init_lock.lock().unwrap_or_else(|e| e.into_inner());
+                match slot.value.get() {
+                    Some(resource) => resource.clone(),
+                    None => {
+                        let factory = slot.factory.as_ref().ok_or_else(|| {
+                            TermenuError::framework_resource_error(Some(json!({
+                                "issue": format!("State '{}' has no factory.", type_name::<T>())
+                            })))
+                        })?;
+                        let resource = factory()?;
+                        let _ = slot.value.set(resource.clone());
+                        resource
+                    }
+                }
+            }
+        };
+
+        resource.downcast::<T>().map_err(|_| {
+            TermenuError::framework_resource_error(Some(json!({
+                "issue": format!("State '{}' has an unexpected type.", type_name::<T>())
+            })))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static BUILT: AtomicUsize = AtomicUsize::new(0);
+
+    struct Pool(u32);
+
+    #[test]
+    fn test_state_is_lazy_and_shared() {
+        let mut state = State::new();
+        state.register(|| {
+            BUILT.fetch_add(1, Ordering::SeqCst);
+            Ok(Pool(7))
+        });
+        assert_eq!(BUILT.load(Ordering::SeqCst), 0);
+
+        let first = state.get::<Pool>().unwrap();
+        let second = state.get::<Pool>().unwrap();
+        assert_eq!(first.0, 7);
+        assert!(Arc::ptr_eq(&first, &second));
+        assert_eq!(BUILT.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_state_missing_resource() {
+        let state = State::new();
+        assert!(state.get::<Pool>().is_err());
+    }
+}
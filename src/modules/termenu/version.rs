@@ -0,0 +1,99 @@
+use colored::Colorize;
+use serde::Serialize;
+use serde_json::json;
+
+use super::app_info::AppInfo;
+use super::core::Termenu;
+use super::exceptions::TermenuError;
+
+/// Version and build details, embedded by `build.rs`
+#[derive(Debug, Clone, Serialize)]
+pub struct BuildInfo {
+    pub name: String,
+    pub version: String,
+    pub commit: String,
+    pub build_date: String,
+    pub target: String,
+    pub profile: String,
+    pub features: Vec<String>,
+}
+
+impl BuildInfo {
+    pub fn current() -> Self {
+        let info = AppInfo::current();
+        Self {
+            name: info.name,
+            version: info.version,
+            commit: env!("TERMENU_GIT_COMMIT").to_string(),
+            build_date: env!("TERMENU_BUILD_DATE").to_string(),
+            target: env!("TERMENU_TARGET").to_string(),
+            profile: env!("TERMENU_PROFILE").to_string(),
+            features: env!("TERMENU_FEATURES")
+                .split(',')
+                .filter(|f| !f.is_empty())
+                .map(str::to_string)
+                .collect(),
+        }
+    }
+
+    /// Print the human readable form
+    pub fn print(&self) {
+        println!("{} {}", self.name.green().bold(), self.version.bold());
+        println!("  {:<10} {}", "commit:", self.commit);
+        println!("  {:<10} {}", "built:", self.build_date);
+        println!("  {:<10} {}", "target:", self.target);
+        println!("  {:<10} {}", "profile:", self.profile);
+        let features = if self.features.is_empty() {
+            "none".to_string()
+        } else {
+            self.features.join(", ")
+        };
+        println!("  {:<10} {}", "features:", features);
+    }
+
+    /// Print as pretty JSON, e.g. for bug reports
+    pub fn print_json(&self) -> Result<(), TermenuError> {
+        let output = serde_json::to_string_pretty(self).map_err(|e| {
+            TermenuError::framework_unknown_error(Some(json!({
+                "issue": format!("Failed to serialize version info: {}", e)
+            })))
+        })?;
+        println!("{}", output);
+        Ok(())
+    }
+}
+
+/// Built-in `version` command
+pub fn register() -> Termenu {
+    let mut command = Termenu::new_command(
+        "version",
+        "Show version and build details.",
+        |options| {
+            let build = BuildInfo::current();
+            match options.get("--format").and_then(|v| v.as_deref()) {
+                Some("text") => {
+                    build.print();
+                    Ok(())
+                }
+                Some("json") => build.print_json(),
+                other => Err(TermenuError::input_unknown_error(Some(json!({
+                    "issue": format!("Unknown format '{}'. Use text or json.", other.unwrap_or_default())
+                })))),
+            }
+        },
+    );
+
+    command
+        .add_option("--format", "Output format.")
+        .choices(&["text", "json"])
+        .default_value("text");
+
+    command
+        .add_example(
+            "version --format=json",
+            "Print build details as JSON for a bug report.",
+        )
+        .error_codes(&["E011"]);
+
+    command
+}
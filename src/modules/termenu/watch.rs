@@ -0,0 +1,130 @@
+use colored::Colorize;
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+use serde_json::json;
+use std::future::Future;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+
+use super::exceptions::TermenuError;
+use super::report::{ErrorFormat, on_stderr};
+
+/// Default quiet period before a burst of changes triggers a re-run
+pub const DEFAULT_DEBOUNCE_MS: u64 = 300;
+
+/// Settings for `--watch` mode
+#[derive(Debug, Clone)]
+pub struct WatchConfig {
+    pub paths: Vec<PathBuf>,
+    pub debounce: Duration,
+    pub clear: bool,
+}
+
+impl WatchConfig {
+    pub fn new(paths: Vec<PathBuf>) -> Self {
+        Self {
+            paths,
+            debounce: Duration::from_millis(DEFAULT_DEBOUNCE_MS),
+            clear: false,
+        }
+    }
+}
+
+/// Run `run` once, then again every time a watched path changes, until Ctrl-C
+pub async fn watch<F, Fut>(config: &WatchConfig, mut run: F) -> Result<(), TermenuError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<(), TermenuError>>,
+{
+    let (tx, mut rx) = mpsc::unbounded_channel::<()>();
+
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<Event>| {
+        if let Ok(event) = res
+            && !matches!(event.kind, EventKind::Access(_))
+        {
+            let _ = tx.send(());
+        }
+    })
+    .map_err(|e| {
+        TermenuError::framework_resource_error(Some(json!({
+            "issue": format!("Failed to start file watcher: {}", e)
+        })))
+    })?;
+
+    for path in &config.paths {
+        watcher.watch(path, RecursiveMode::Recursive).map_err(|e| {
+            TermenuError::input_unknown_error(Some(json!({
+                "issue": format!("Cannot watch '{}': {}", path.display(), e)
+            })))
+        })?;
+    }
+
+    let watched = config
+        .paths
+        .iter()
+        .map(|p| p.display().to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let mut run_count: u64 = 0;
+    loop {
+        run_count += 1;
+        if config.clear {
+            print!("\x1B[2J\x1B[1;1H");
+        }
+        println!(
+            "{} Run #{} {}",
+            "▶".cyan(),
+            run_count,
+            format!("(watching {})", watched).dimmed()
+        );
+
+        let started = Instant::now();
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => break,
+            result = run() => report_run(result, started.elapsed()),
+        }
+
+        println!("{}", "Waiting for changes... (Ctrl-C to exit)".dimmed());
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => break,
+            changed = rx.recv() => {
+                if changed.is_none() {
+                    break;
+                }
+            }
+        }
+
+        // Swallow the rest of the burst until the paths have been quiet long enough
+        while let Ok(Some(())) = tokio::time::timeout(config.debounce, rx.recv()).await {}
+    }
+
+    println!("\n{} Watch mode stopped.", "■".yellow());
+    Ok(())
+}
+
+fn report_run(result: Result<(), TermenuError>, elapsed: Duration) {
+    match result {
+        Ok(()) => println!(
+            "{} Completed in {}",
+            "✔".green(),
+            format_duration(elapsed).bold()
+        ),
+        Err(e) if ErrorFormat::current() == ErrorFormat::Json => e.report(),
+        Err(e) => eprintln!(
+            "{} Failed in {}: {}",
+            on_stderr("✖".red()),
+            on_stderr(format_duration(elapsed).bold()),
+            e
+        ),
+    }
+}
+
+/// Human readable duration, e.g. `850ms` or `2.31s`
+pub fn format_duration(elapsed: Duration) -> String {
+    if elapsed.as_secs() == 0 {
+        format!("{}ms", elapsed.as_millis())
+    } else {
+        format!("{:.2}s", elapsed.as_secs_f64())
+    }
+}